@@ -0,0 +1,415 @@
+use crate::datetime::{
+    decode_days, decode_hours, decode_minutes, decode_months, decode_seconds, decode_weekday,
+    encode_bcd, encode_hours, encode_weekday,
+};
+use crate::register_access::{Flags, InterruptPin, InterruptReg, Register, RegisterAccess};
+use crate::{Error, Pcf85263a};
+
+use chrono::Weekday;
+
+pub(crate) struct AlarmEnables;
+
+impl AlarmEnables {
+    pub const SEC_A1E: u8 = 0;
+    pub const MIN_A1E: u8 = 1;
+    pub const HR_A1E: u8 = 2;
+    pub const DAY_A1E: u8 = 3;
+    pub const MON_A1E: u8 = 4;
+    pub const MIN_A2E: u8 = 5;
+    pub const HR_A2E: u8 = 6;
+    pub const WDAY_A2E: u8 = 7;
+    pub const ALARM1_MASK: u8 = 0b00011111;
+    pub const ALARM2_MASK: u8 = 0b11100000;
+}
+
+/// Alarm 1 configuration.
+///
+/// Every field set to `Some` is enabled for matching, the alarm fires once
+/// all enabled fields match the current time. A field set to `None` is ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alarm1 {
+    pub second: Option<u8>,
+    pub minute: Option<u8>,
+    /// Hour in 24h format, converted to 12h format if the clock runs in 12h mode
+    pub hour: Option<u8>,
+    pub day: Option<u8>,
+    pub month: Option<u8>,
+}
+
+impl Alarm1 {
+    fn is_valid(&self) -> bool {
+        self.second.is_none_or(|s| s <= 59)
+            && self.minute.is_none_or(|m| m <= 59)
+            && self.hour.is_none_or(|h| h <= 23)
+            && self.day.is_none_or(|d| (1..=31).contains(&d))
+            && self.month.is_none_or(|m| (1..=12).contains(&m))
+    }
+
+    /// Encodes the alarm into the values for the registers `SECOND_ALARM1`
+    /// to `MONTH_ALARM1` and the alarm 1 bits of `ALARM_ENABLES`.
+    pub(crate) fn encode(&self, clock_12h: bool) -> Option<([u8; 5], u8)> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        let enable = |field: Option<u8>, bit: u8| if field.is_some() { 1 << bit } else { 0 };
+
+        let values = [
+            encode_bcd(self.second.unwrap_or(0)),
+            encode_bcd(self.minute.unwrap_or(0)),
+            encode_hours(self.hour.unwrap_or(0), clock_12h),
+            encode_bcd(self.day.unwrap_or(1)),
+            encode_bcd(self.month.unwrap_or(1)),
+        ];
+        let enables = enable(self.second, AlarmEnables::SEC_A1E)
+            | enable(self.minute, AlarmEnables::MIN_A1E)
+            | enable(self.hour, AlarmEnables::HR_A1E)
+            | enable(self.day, AlarmEnables::DAY_A1E)
+            | enable(self.month, AlarmEnables::MON_A1E);
+
+        Some((values, enables))
+    }
+
+    pub(crate) fn decode(values: [u8; 5], enables: u8, clock_12h: bool) -> Self {
+        let [second, minute, hour, day, month] = values;
+        let field = |bit: u8, value: u8| (enables & (1 << bit) > 0).then_some(value);
+
+        Self {
+            second: field(AlarmEnables::SEC_A1E, decode_seconds(second)),
+            minute: field(AlarmEnables::MIN_A1E, decode_minutes(minute)),
+            hour: field(AlarmEnables::HR_A1E, decode_hours(hour, clock_12h).as_24h()),
+            day: field(AlarmEnables::DAY_A1E, decode_days(day)),
+            month: field(AlarmEnables::MON_A1E, decode_months(month)),
+        }
+    }
+}
+
+/// Set of weekdays
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    pub const ALL: Self = Self(0b01111111);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn with(self, weekday: Weekday) -> Self {
+        Self(self.0 | (1 << encode_weekday(weekday)))
+    }
+
+    pub fn without(self, weekday: Weekday) -> Self {
+        Self(self.0 & !(1 << encode_weekday(weekday)))
+    }
+
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.0 & (1 << encode_weekday(weekday)) > 0
+    }
+
+    /// Returns the weekday if the set contains exactly one
+    pub fn single(&self) -> Option<Weekday> {
+        (self.0.count_ones() == 1).then(|| decode_weekday(self.0.trailing_zeros() as u8))
+    }
+}
+
+impl From<Weekday> for Weekdays {
+    fn from(weekday: Weekday) -> Self {
+        Self::empty().with(weekday)
+    }
+}
+
+/// Alarm 2 configuration.
+///
+/// Like [`Alarm1`], fields set to `None` are not matched. The chip compares
+/// against a single weekday register, so `weekdays` has to contain either
+/// exactly one day or all of them ([`Weekdays::ALL`] disables weekday matching).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alarm2 {
+    pub minute: Option<u8>,
+    /// Hour in 24h format, converted to 12h format if the clock runs in 12h mode
+    pub hour: Option<u8>,
+    pub weekdays: Weekdays,
+}
+
+impl Default for Alarm2 {
+    fn default() -> Self {
+        Self {
+            minute: None,
+            hour: None,
+            weekdays: Weekdays::ALL,
+        }
+    }
+}
+
+impl Alarm2 {
+    /// Encodes the alarm into the values for the registers `MINUTE_ALARM2`
+    /// to `WEEKDAY_ALARM2` and the alarm 2 bits of `ALARM_ENABLES`.
+    pub(crate) fn encode(&self, clock_12h: bool) -> Option<([u8; 3], u8)> {
+        if !(self.minute.is_none_or(|m| m <= 59) && self.hour.is_none_or(|h| h <= 23)) {
+            return None;
+        }
+
+        let weekday = if self.weekdays == Weekdays::ALL {
+            None
+        } else {
+            Some(self.weekdays.single()?)
+        };
+
+        let enable = |enabled: bool, bit: u8| if enabled { 1 << bit } else { 0 };
+
+        let values = [
+            encode_bcd(self.minute.unwrap_or(0)),
+            encode_hours(self.hour.unwrap_or(0), clock_12h),
+            weekday.map_or(0, encode_weekday),
+        ];
+        let enables = enable(self.minute.is_some(), AlarmEnables::MIN_A2E)
+            | enable(self.hour.is_some(), AlarmEnables::HR_A2E)
+            | enable(weekday.is_some(), AlarmEnables::WDAY_A2E);
+
+        Some((values, enables))
+    }
+
+    pub(crate) fn decode(values: [u8; 3], enables: u8, clock_12h: bool) -> Self {
+        let [minute, hour, weekday] = values;
+        let enabled = |bit: u8| enables & (1 << bit) > 0;
+
+        Self {
+            minute: enabled(AlarmEnables::MIN_A2E).then_some(decode_minutes(minute)),
+            hour: enabled(AlarmEnables::HR_A2E).then_some(decode_hours(hour, clock_12h).as_24h()),
+            weekdays: if enabled(AlarmEnables::WDAY_A2E) {
+                decode_weekday(weekday).into()
+            } else {
+                Weekdays::ALL
+            },
+        }
+    }
+}
+
+/// Implements the alarm methods of `$driver`, see `impl_register_access`
+macro_rules! impl_alarm {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Configures alarm 1.
+            ///
+            /// The alarm is disabled while its registers are updated, so no
+            /// intermediate state can trigger it.
+            pub $($async)? fn set_alarm1(&mut self, alarm: &Alarm1) -> Result<(), Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let (values, enables) = alarm.encode(clock_12h).ok_or(Error::InvalidAlarm)?;
+
+                let other_enables = self.read_register(Register::ALARM_ENABLES)$($await)*?
+                    & !AlarmEnables::ALARM1_MASK;
+                self.write_register(Register::ALARM_ENABLES, other_enables)$($await)*?;
+                self.write_register_multiple(Register::SECOND_ALARM1, &values)$($await)*?;
+                self.write_register(Register::ALARM_ENABLES, other_enables | enables)$($await)*
+            }
+
+            pub $($async)? fn alarm1(&mut self) -> Result<Alarm1, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let values = self.read_register_multiple(Register::SECOND_ALARM1)$($await)*?;
+                let enables = self.read_register(Register::ALARM_ENABLES)$($await)*?;
+
+                Ok(Alarm1::decode(values, enables, clock_12h))
+            }
+
+            pub $($async)? fn clear_alarm1_flag(&mut self) -> Result<(), Error<E>> {
+                self.clear_flags(Flags::default().with_alarm1(true))$($await)*
+            }
+
+            /// Configures alarm 2 and routes its interrupt to `pin` like
+            /// [`Pcf85263a::route_interrupt`].
+            ///
+            /// The alarm 2 interrupt is disabled on the other pin, or on both pins
+            /// if `pin` is `None`. Returns [`Error::PinConflict`] without writing
+            /// anything if `pin` can't be switched to interrupt output, e.g.
+            /// because INTA is still a clock output as after power-on.
+            pub $($async)? fn set_alarm2(
+                &mut self,
+                alarm: &Alarm2,
+                pin: Option<InterruptPin>,
+            ) -> Result<(), Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let (values, enables) = alarm.encode(clock_12h).ok_or(Error::InvalidAlarm)?;
+
+                if let Some(pin) = pin {
+                    self.set_interrupt_output(pin)$($await)*?;
+                }
+
+                let other_enables = self.read_register(Register::ALARM_ENABLES)$($await)*?
+                    & !AlarmEnables::ALARM2_MASK;
+                self.write_register(Register::ALARM_ENABLES, other_enables)$($await)*?;
+                self.write_register_multiple(Register::MINUTE_ALARM2, &values)$($await)*?;
+                self.write_register(Register::ALARM_ENABLES, other_enables | enables)$($await)*?;
+
+                self.set_interrupt_enable(InterruptReg::A2IE, pin)$($await)*
+            }
+
+            pub $($async)? fn alarm2(&mut self) -> Result<Alarm2, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let values = self.read_register_multiple(Register::MINUTE_ALARM2)$($await)*?;
+                let enables = self.read_register(Register::ALARM_ENABLES)$($await)*?;
+
+                Ok(Alarm2::decode(values, enables, clock_12h))
+            }
+
+            pub $($async)? fn clear_alarm2_flag(&mut self) -> Result<(), Error<E>> {
+                self.clear_flags(Flags::default().with_alarm2(true))$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_alarm;
+
+impl_alarm!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_alarm1_encode() {
+        let alarm = Alarm1 {
+            second: Some(30),
+            minute: Some(15),
+            hour: Some(18),
+            day: None,
+            month: Some(12),
+        };
+
+        let (values, enables) = alarm.encode(false).unwrap();
+        assert_eq!(values, [0x30, 0x15, 0x18, 0x01, 0x12]);
+        assert_eq!(enables, 0b00010111);
+        assert_eq!(Alarm1::decode(values, enables, false), alarm);
+
+        // 6 PM
+        let (values, enables) = alarm.encode(true).unwrap();
+        assert_eq!(values, [0x30, 0x15, 0x26, 0x01, 0x12]);
+        assert_eq!(Alarm1::decode(values, enables, true), alarm);
+    }
+
+    #[test]
+    fn test_alarm1_invalid() {
+        let alarm = Alarm1 {
+            minute: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(alarm.encode(false), None);
+
+        let alarm = Alarm1 {
+            day: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(alarm.encode(false), None);
+    }
+
+    #[test]
+    fn test_alarm2_encode() {
+        let alarm = Alarm2 {
+            minute: Some(30),
+            hour: Some(2),
+            weekdays: Weekday::Sat.into(),
+        };
+
+        let (values, enables) = alarm.encode(false).unwrap();
+        assert_eq!(values, [0x30, 0x02, 0x06]);
+        assert_eq!(enables, 0b11100000);
+        assert_eq!(Alarm2::decode(values, enables, false), alarm);
+
+        // 2 AM
+        let (values, enables) = alarm.encode(true).unwrap();
+        assert_eq!(values, [0x30, 0x02, 0x06]);
+        assert_eq!(Alarm2::decode(values, enables, true), alarm);
+        // 2 PM
+        assert_eq!(
+            Alarm2::decode([0x30, 0x22, 0x06], enables, true).hour,
+            Some(14)
+        );
+
+        let alarm = Alarm2 {
+            minute: Some(0),
+            ..Default::default()
+        };
+        let (values, enables) = alarm.encode(false).unwrap();
+        assert_eq!(enables, 0b00100000);
+        assert_eq!(Alarm2::decode(values, enables, false), alarm);
+
+        let alarm = Alarm2 {
+            weekdays: Weekdays::from(Weekday::Mon).with(Weekday::Tue),
+            ..Default::default()
+        };
+        assert_eq!(alarm.encode(false), None);
+    }
+
+    #[test]
+    fn test_set_alarm1() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0xFF]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xE0]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x08]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00, 0x45, 0x07, 0x01, 0x01]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xE6]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.set_alarm1(&Alarm1 {
+            minute: Some(45),
+            hour: Some(7),
+            ..Default::default()
+        })
+        .unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_set_alarm2() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            // power-on value, INTA is a clock output and the TS pin is disabled
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x04]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0x01]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x0D]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00, 0x03, 0x00]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0x61]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x11]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x11]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x08]),
+            // INTA is still a clock output, nothing is written
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let alarm = Alarm2 {
+            minute: Some(0),
+            hour: Some(3),
+            ..Default::default()
+        };
+        rtc.set_alarm2(&alarm, Some(InterruptPin::IntB)).unwrap();
+        assert!(matches!(
+            rtc.set_alarm2(&alarm, Some(InterruptPin::IntA)),
+            Err(Error::PinConflict)
+        ));
+
+        rtc.release().release().done();
+    }
+}
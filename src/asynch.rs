@@ -0,0 +1,295 @@
+//! Async driver built on `embedded-hal-async`, mirroring the blocking
+//! [`crate::Pcf85263a`].
+//!
+//! Both drivers are generated from the same source, so they share the
+//! register sequencing, including the stop-watch mode, and differ only in
+//! `async` and `.await`. The exceptions are
+//! [`Pcf85263a::set_datetime_on_pps`], which takes an async closure, and
+//! [`TemperatureCompensator::update_async`]. The async driver doesn't
+//! implement [`rtcc::DateTimeAccess`], as that trait is blocking.
+
+use core::future::Future;
+use core::time::Duration;
+
+// the shared implementations resolve names where they're expanded
+use crate::alarm::{impl_alarm, AlarmEnables};
+use crate::calibration::{drift_ppb, impl_calibration};
+use crate::compensation::{fast_offset_mode, impl_compensation};
+use crate::config::impl_config;
+use crate::datetime::{
+    decode_checked_datetime, decode_date, decode_datetime, decode_full_datetime, decode_time,
+    encode_date, encode_full_datetime, encode_time, impl_datetime, OS_FLAG,
+};
+use crate::interrupt::{impl_interrupt, interrupt_pinio, periodic_interrupt_reg};
+use crate::ram::{impl_ram, pack_nibble, unpack_nibble};
+use crate::register_access::{
+    check_power_on_config, decode_frequency_offset, encode_frequency_offset, impl_register_access,
+    BatterySwitchReg, Flags, FunctionReg, InterruptPin, InterruptReg, OscillatorReg, PinIoReg,
+    Register, ResetCommand, WatchdogReg,
+};
+use crate::stopwatch::{decode_elapsed, encode_alarm1, encode_alarm2, impl_stopwatch};
+use crate::timestamp::impl_timestamp;
+use crate::watchdog::impl_watchdog;
+use crate::{
+    frequency_offset_value, Alarm1, Alarm2, CalibrationSample, Config, DriftCalibration, Error,
+    Event, EventSet, NaiveDate, NaiveDateTime, NaiveTime, OffsetMode, PeriodicInterrupt, RamValue,
+    Signal, TamperEvent, TemperatureCompensator, TimestampMode, TsInputConfig, TsrSlot,
+    WatchdogConfig, DEFAULT_ADDRESS,
+};
+
+use embedded_hal_async::i2c::Operation;
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncRegisterAccess {
+    type Error;
+
+    async fn write_register(&mut self, register: u8, value: u8) -> Result<(), Self::Error>;
+    /// See [`crate::RegisterAccess::write_registers`]
+    async fn write_registers(
+        &mut self,
+        start_register: u8,
+        values: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, Self::Error>;
+    async fn read_registers(
+        &mut self,
+        start_register: u8,
+        values: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+pub struct AsyncI2cInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C> AsyncI2cInterface<I2C> {
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> AsyncRegisterAccess for AsyncI2cInterface<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c<Error = E>,
+{
+    type Error = E;
+
+    async fn write_register(&mut self, register: u8, value: u8) -> Result<(), Self::Error> {
+        let payload = [register, value];
+
+        self.i2c.write(self.address, &payload).await
+    }
+
+    async fn write_registers(
+        &mut self,
+        start_register: u8,
+        values: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    Operation::Write(&[start_register]),
+                    Operation::Write(values),
+                ],
+            )
+            .await
+    }
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
+        let mut value = [0u8; 1];
+
+        self.read_registers(register, &mut value).await?;
+
+        Ok(value[0])
+    }
+
+    async fn read_registers(
+        &mut self,
+        start_register: u8,
+        values: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.i2c
+            .write_read(self.address, &[start_register], values)
+            .await
+    }
+}
+
+/// Async twin of [`crate::Pcf85263a`]
+pub struct Pcf85263a<I> {
+    interface: I,
+    /// 12/24h mode of the clock, read from the oscillator register on first
+    /// use. Cleared by `invalidate_cache`.
+    clock_12h: Option<bool>,
+    /// 100th seconds mode, read from the function register on first use.
+    /// Cleared by `invalidate_cache`.
+    s100th: Option<bool>,
+}
+
+impl<I, E> Pcf85263a<I>
+where
+    I: AsyncRegisterAccess<Error = E>,
+{
+    pub fn new(interface: I) -> Self {
+        Pcf85263a {
+            interface,
+            clock_12h: None,
+            s100th: None,
+        }
+    }
+
+    pub fn release(self) -> I {
+        self.interface
+    }
+}
+
+impl<I2C, E> Pcf85263a<AsyncI2cInterface<I2C>>
+where
+    I2C: embedded_hal_async::i2c::I2c<Error = E>,
+{
+    pub fn new_with_i2c(i2c: I2C) -> Self {
+        Self::new(AsyncI2cInterface::new(i2c, DEFAULT_ADDRESS))
+    }
+}
+
+/// Async twin of [`crate::Stopwatch`], created with [`Pcf85263a::into_stopwatch`]
+pub struct Stopwatch<I> {
+    rtc: Pcf85263a<I>,
+}
+
+impl_register_access!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_datetime!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_alarm!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_timestamp!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_watchdog!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_ram!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_config!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_interrupt!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_calibration!(Pcf85263a, AsyncRegisterAccess, [async], [.await]);
+impl_compensation!(Pcf85263a, AsyncRegisterAccess, update_async, [async], [.await]);
+impl_stopwatch!(Pcf85263a, Stopwatch, AsyncRegisterAccess, [async], [.await]);
+
+impl<I, E> Pcf85263a<I>
+where
+    I: AsyncRegisterAccess<Error = E>,
+{
+    /// See [`crate::Pcf85263a::set_datetime`]
+    pub async fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Error<E>> {
+        self.set_datetime_on_pps(datetime, || async {}).await
+    }
+
+    /// Like [`crate::Pcf85263a::set_datetime_on_pps`], but `wait_for_pps`
+    /// returns a future that should complete on the edge
+    pub async fn set_datetime_on_pps<F, Fut>(
+        &mut self,
+        datetime: &NaiveDateTime,
+        wait_for_pps: F,
+    ) -> Result<(), Error<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        self.write_datetime_stopped(datetime).await?;
+        wait_for_pps().await;
+        self.write_stop_register(false).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    /// The mock never returns `Pending`, so polling until ready is enough
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_read_register() {
+        let expectations = [I2cTransaction::write_read(
+            DEFAULT_ADDRESS,
+            vec![Register::FLAGS],
+            vec![0x40],
+        )];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new_with_i2c(i2c);
+
+        assert!(block_on(rtc.read_flags()).unwrap().alarm2());
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_async_read_registers() {
+        let expectations = [I2cTransaction::write_read(
+            DEFAULT_ADDRESS,
+            vec![Register::DAYS],
+            vec![0x17, 0x06, 0x10, 0x26],
+        )];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new_with_i2c(i2c);
+
+        assert_eq!(
+            block_on(rtc.date()).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+        );
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_async_write_register() {
+        let expectations = [I2cTransaction::write(
+            DEFAULT_ADDRESS,
+            vec![Register::FLAGS, 0xBF],
+        )];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new_with_i2c(i2c);
+
+        block_on(rtc.clear_alarm2_flag()).unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_async_write_registers() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x90]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE], vec![0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0xA4]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::SECONDS_100TH]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00; 6]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let rtc = Pcf85263a::new_with_i2c(i2c);
+
+        let mut stopwatch = block_on(rtc.into_stopwatch()).ok().unwrap();
+        block_on(stopwatch.reset()).unwrap();
+
+        stopwatch.release().release().done();
+    }
+}
@@ -0,0 +1,184 @@
+use crate::register_access::RegisterAccess;
+use crate::{
+    checked_offset_value_for_ppb_offset, ppb_offset_for_offset_value, Error, NaiveDateTime,
+    OffsetMode, Pcf85263a,
+};
+
+/// Reference time and RTC time, taken at the same moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationSample {
+    pub reference: NaiveDateTime,
+    pub rtc: NaiveDateTime,
+}
+
+/// Result of a drift calibration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriftCalibration {
+    /// Drift observed between the samples in ppb, positive if the RTC ran fast
+    pub observed_ppb: i32,
+    /// Newly programmed frequency offset correction in ppb
+    pub offset_ppb: i32,
+    pub offset_mode: OffsetMode,
+}
+
+/// Returns the drift of the RTC between two samples in ppb, positive if the
+/// RTC ran fast. Returns `None` if the reference time doesn't advance.
+pub(crate) fn drift_ppb(first: &CalibrationSample, second: &CalibrationSample) -> Option<i32> {
+    let reference = (second.reference - first.reference).num_milliseconds() as i128;
+    let rtc = (second.rtc - first.rtc).num_milliseconds() as i128;
+
+    if reference <= 0 {
+        return None;
+    }
+
+    i32::try_from((rtc - reference) * 1_000_000_000 / reference).ok()
+}
+
+/// Picks the offset mode that gets closest to `offset_ppb`, preferring the
+/// less power hungry normal mode. Returns `None` if it's out of range for both.
+pub(crate) fn best_offset_mode(offset_ppb: i32) -> Option<OffsetMode> {
+    [OffsetMode::Normal, OffsetMode::Fast]
+        .into_iter()
+        .filter_map(|mode| {
+            let offset = checked_offset_value_for_ppb_offset(offset_ppb, mode)?;
+            let residual = (ppb_offset_for_offset_value(offset, mode) - offset_ppb).abs();
+
+            Some((mode, residual))
+        })
+        .min_by_key(|&(_, residual)| residual)
+        .map(|(mode, _)| mode)
+}
+
+impl DriftCalibration {
+    /// Computes the correction for `observed_ppb` on top of the currently
+    /// programmed `offset_ppb`. Returns [`Error::InvalidOffset`] if it's out
+    /// of range for both offset modes.
+    pub(crate) fn new<E>(observed_ppb: i32, offset_ppb: i32) -> Result<Self, Error<E>> {
+        let offset_ppb = offset_ppb
+            .checked_sub(observed_ppb)
+            .ok_or(Error::InvalidOffset)?;
+        let offset_mode = best_offset_mode(offset_ppb).ok_or(Error::InvalidOffset)?;
+
+        Ok(DriftCalibration {
+            observed_ppb,
+            offset_ppb,
+            offset_mode,
+        })
+    }
+}
+
+/// Implements the calibration methods of `$driver`, see `impl_register_access`
+macro_rules! impl_calibration {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Corrects the frequency offset from the drift observed between two samples.
+            ///
+            /// The correction that was programmed while the samples were taken is
+            /// read back and taken into account, so the calibration can be repeated
+            /// after every sync. The samples should be some hours apart, as a single
+            /// offset step is about 2 ppm, i.e. 7 ms per hour.
+            ///
+            /// Returns [`Error::InvalidCalibration`] if the reference time doesn't
+            /// advance between the samples, and [`Error::InvalidOffset`] if the drift
+            /// is too large to be corrected.
+            pub $($async)? fn calibrate_drift(
+                &mut self,
+                first: &CalibrationSample,
+                second: &CalibrationSample,
+            ) -> Result<DriftCalibration, Error<E>> {
+                let observed_ppb = drift_ppb(first, second).ok_or(Error::InvalidCalibration)?;
+                let offset_ppb = self.frequency_offset_ppb()$($await)*?;
+                let calibration = DriftCalibration::new(observed_ppb, offset_ppb)?;
+
+                self.set_frequency_offset_ppb(calibration.offset_ppb, calibration.offset_mode)
+                    $($await)*?;
+
+                Ok(calibration)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_calibration;
+
+impl_calibration!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_access::Register;
+    use crate::{I2cInterface, NaiveDate, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    fn sample(reference_secs: u32, rtc_millis: u32) -> CalibrationSample {
+        let midnight = NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        CalibrationSample {
+            reference: midnight + chrono::TimeDelta::seconds(reference_secs.into()),
+            rtc: midnight + chrono::TimeDelta::milliseconds(rtc_millis.into()),
+        }
+    }
+
+    #[test]
+    fn test_drift_ppb() {
+        // 10 ms fast over 1000 s
+        assert_eq!(
+            drift_ppb(&sample(0, 0), &sample(1_000, 1_000_010)),
+            Some(10_000)
+        );
+        // 86.4 ms slow over a day
+        assert_eq!(
+            drift_ppb(&sample(0, 0), &sample(86_400, 86_399_914)),
+            Some(-995)
+        );
+        assert_eq!(drift_ppb(&sample(10, 0), &sample(10, 1_000)), None);
+    }
+
+    #[test]
+    fn test_best_offset_mode() {
+        assert_eq!(best_offset_mode(0), Some(OffsetMode::Normal));
+        assert_eq!(best_offset_mode(4_340), Some(OffsetMode::Normal));
+        assert_eq!(best_offset_mode(-4_069), Some(OffsetMode::Fast));
+        // only in range for normal mode
+        assert_eq!(best_offset_mode(270_000), Some(OffsetMode::Normal));
+        assert_eq!(best_offset_mode(300_000), None);
+    }
+
+    #[test]
+    fn test_calibrate_drift() {
+        let expectations = [
+            // 2 steps in normal mode, +4.34 ppm
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OFFSET], vec![0x02, 0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OFFSET]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0xFE, 0x40]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        // 8.41 ppm fast over 100,000 s
+        let calibration = rtc
+            .calibrate_drift(&sample(0, 0), &sample(100_000, 100_000_841))
+            .unwrap();
+        assert_eq!(
+            calibration,
+            DriftCalibration {
+                observed_ppb: 8_410,
+                offset_ppb: -4_070,
+                offset_mode: OffsetMode::Fast,
+            }
+        );
+
+        rtc.release().release().done();
+    }
+}
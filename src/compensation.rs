@@ -0,0 +1,196 @@
+use crate::register_access::{OscillatorReg, RegisterAccess};
+use crate::{
+    offset_value_for_ppb_offset, ppb_offset_for_offset_value, Error, OffsetMode, Pcf85263a,
+};
+
+/// Frequency deviation of a typical tuning fork crystal, -0.034 ppm/°C²
+pub const DEFAULT_COEFFICIENT_PPB: i32 = -34;
+
+/// Returns `osc` switched to fast offset mode, or `None` if it already is
+pub(crate) fn fast_offset_mode(osc: OscillatorReg) -> Option<OscillatorReg> {
+    (osc.offset_mode() != OffsetMode::Fast).then(|| osc.with_offset_mode(OffsetMode::Fast))
+}
+
+/// Compensates the parabolic temperature curve of a tuning fork crystal
+/// through the offset register.
+///
+/// The offset is corrected in fast mode, so new values take effect within
+/// minutes. Temperatures are given in milli-°C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemperatureCompensator {
+    turnover_millicelsius: i32,
+    coefficient_ppb: i32,
+    base_offset_ppb: i32,
+    /// Last offset register value written, `None` until the offset mode is set
+    last_offset: Option<i8>,
+}
+
+impl TemperatureCompensator {
+    /// Creates a compensator for a crystal with the turnover temperature
+    /// `turnover_millicelsius` and the default coefficient.
+    pub fn new(turnover_millicelsius: i32) -> Self {
+        TemperatureCompensator {
+            turnover_millicelsius,
+            coefficient_ppb: DEFAULT_COEFFICIENT_PPB,
+            base_offset_ppb: 0,
+            last_offset: None,
+        }
+    }
+
+    /// Sets the temperature coefficient in ppb/°C²
+    pub fn with_coefficient(self, coefficient_ppb: i32) -> Self {
+        Self {
+            coefficient_ppb,
+            ..self
+        }
+    }
+
+    /// Sets a correction in ppb applied on top of the temperature
+    /// compensation, e.g. for the offset of the crystal at its turnover
+    /// temperature.
+    pub fn with_base_offset(self, base_offset_ppb: i32) -> Self {
+        Self {
+            base_offset_ppb,
+            ..self
+        }
+    }
+
+    /// Returns the correction in ppb for `temperature_millicelsius`, clamped
+    /// to what the offset register can correct in fast mode.
+    pub fn offset_ppb(&self, temperature_millicelsius: i32) -> i32 {
+        let min = ppb_offset_for_offset_value(i8::MIN, OffsetMode::Fast) as i64;
+        let max = ppb_offset_for_offset_value(i8::MAX, OffsetMode::Fast) as i64;
+
+        let delta = temperature_millicelsius as i64 - self.turnover_millicelsius as i64;
+        let deviation =
+            (self.coefficient_ppb as i64).saturating_mul(delta.saturating_mul(delta)) / 1_000_000;
+
+        (self.base_offset_ppb as i64)
+            .saturating_sub(deviation)
+            .clamp(min, max) as i32
+    }
+
+    /// Returns the offset register value for `temperature_millicelsius`, or
+    /// `None` if it was already written. Corrections beyond the range of the
+    /// offset register are clamped.
+    pub(crate) fn pending_offset(&self, temperature_millicelsius: i32) -> Option<i8> {
+        let offset = offset_value_for_ppb_offset(
+            self.offset_ppb(temperature_millicelsius),
+            OffsetMode::Fast,
+        );
+
+        (self.last_offset != Some(offset)).then_some(offset)
+    }
+
+    /// Forgets the last written value, so the next update writes the offset
+    /// mode and register again, e.g. after a reset of the chip.
+    pub fn invalidate(&mut self) {
+        self.last_offset = None;
+    }
+
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.last_offset.is_some()
+    }
+
+    pub(crate) fn set_written(&mut self, offset: i8) {
+        self.last_offset = Some(offset);
+    }
+}
+
+/// Implements `TemperatureCompensator::$update` for `$driver`, see
+/// `impl_register_access`
+macro_rules! impl_compensation {
+    ($driver:ident, $access:ident, $update:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl TemperatureCompensator {
+            /// Updates the offset register for `temperature_millicelsius`.
+            ///
+            /// The first call switches the oscillator to fast offset mode, later
+            /// calls only write the offset register if its value changes. Returns
+            /// `true` if it was written.
+            pub $($async)? fn $update<I, E>(
+                &mut self,
+                rtc: &mut $driver<I>,
+                temperature_millicelsius: i32,
+            ) -> Result<bool, Error<E>>
+            where
+                I: $access<Error = E>,
+            {
+                let Some(offset) = self.pending_offset(temperature_millicelsius) else {
+                    return Ok(false);
+                };
+
+                if !self.is_initialized() {
+                    if let Some(osc) = fast_offset_mode(rtc.read_oscillator_register()$($await)*?) {
+                        rtc.write_oscillator_register(osc)$($await)*?;
+                    }
+                }
+
+                rtc.write_offset_register(offset)$($await)*?;
+                self.set_written(offset);
+
+                Ok(true)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_compensation;
+
+impl_compensation!(Pcf85263a, RegisterAccess, update, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_access::Register;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_offset_ppb() {
+        let compensator = TemperatureCompensator::new(25_000);
+
+        assert_eq!(compensator.offset_ppb(25_000), 0);
+        assert_eq!(compensator.offset_ppb(5_000), 13_600);
+        assert_eq!(compensator.offset_ppb(45_000), 13_600);
+        assert_eq!(compensator.offset_ppb(-40_000), 143_650);
+
+        let compensator = compensator.with_coefficient(-40).with_base_offset(-2_000);
+        assert_eq!(compensator.offset_ppb(25_000), -2_000);
+        assert_eq!(compensator.offset_ppb(15_000), 2_000);
+    }
+
+    #[test]
+    fn test_offset_ppb_extreme_temperature() {
+        let compensator = TemperatureCompensator::new(i32::MAX);
+        assert_eq!(compensator.offset_ppb(i32::MIN), 258_382);
+        assert_eq!(compensator.offset_ppb(-100_000), 258_382);
+
+        let compensator = TemperatureCompensator::new(i32::MIN)
+            .with_coefficient(i32::MAX)
+            .with_base_offset(i32::MIN);
+        assert_eq!(compensator.offset_ppb(i32::MAX), -260_416);
+        assert_eq!(compensator.offset_ppb(i32::MIN), -260_416);
+    }
+
+    #[test]
+    fn test_update() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OSCILLATOR, 0x40]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OFFSET, 0x07]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OFFSET, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+        let mut compensator = TemperatureCompensator::new(25_000);
+
+        assert!(compensator.update(&mut rtc, 5_000).unwrap());
+        // still rounds to 7 steps
+        assert!(!compensator.update(&mut rtc, 5_100).unwrap());
+        assert!(compensator.update(&mut rtc, 25_000).unwrap());
+
+        rtc.release().release().done();
+    }
+}
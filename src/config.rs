@@ -0,0 +1,391 @@
+use crate::register_access::{
+    BatterySwitchMode, BatterySwitchReg, BatterySwitchThreshold, ClockOutputFrequency,
+    CrystalDrive, FunctionReg, IntAPinMode, InterruptPin, InterruptReg, LoadCapacitance,
+    OscillatorReg, PeriodicInterrupt, PinIoReg, Register, RegisterAccess, TsPinMode, WatchdogReg,
+};
+use crate::{
+    frequency_offset_value, ppb_offset_for_offset_value, Error, OffsetMode, Pcf85263a,
+    WatchdogConfig,
+};
+
+/// Signal on the interrupt pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Short pulse for every interrupt
+    Pulse,
+    /// Held until the flag is cleared
+    Level,
+}
+
+/// Pin each interrupt is routed to, `None` disables it.
+///
+/// The default matches the power-on state of the chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterruptRouting {
+    pub periodic: Option<InterruptPin>,
+    pub offset_correction: Option<InterruptPin>,
+    pub alarm1: Option<InterruptPin>,
+    pub alarm2: Option<InterruptPin>,
+    /// Shared by all three timestamp registers
+    pub timestamp: Option<InterruptPin>,
+    pub battery_switch: Option<InterruptPin>,
+    pub watchdog: Option<InterruptPin>,
+    pub inta_signal: Signal,
+    pub intb_signal: Signal,
+}
+
+impl Default for InterruptRouting {
+    fn default() -> Self {
+        InterruptRouting {
+            periodic: None,
+            offset_correction: None,
+            alarm1: None,
+            alarm2: None,
+            timestamp: None,
+            battery_switch: None,
+            watchdog: None,
+            inta_signal: Signal::Pulse,
+            intb_signal: Signal::Pulse,
+        }
+    }
+}
+
+impl InterruptRouting {
+    /// Interrupt enable register of `pin`
+    pub(crate) fn register(&self, pin: InterruptPin) -> InterruptReg {
+        let signal = match pin {
+            InterruptPin::IntA => self.inta_signal,
+            InterruptPin::IntB => self.intb_signal,
+        };
+        let on = |route: Option<InterruptPin>| route == Some(pin);
+
+        InterruptReg::default()
+            .with_level(signal == Signal::Level)
+            .with_periodic_interrupt(on(self.periodic))
+            .with_offset_correction_interrupt(on(self.offset_correction))
+            .with_alarm1_interrupt(on(self.alarm1))
+            .with_alarm2_interrupt(on(self.alarm2))
+            .with_timestamp_interrupt(on(self.timestamp))
+            .with_battery_switch_interrupt(on(self.battery_switch))
+            .with_watchdog_interrupt(on(self.watchdog))
+    }
+
+    /// Reconstructs the routing from both interrupt enable registers. An
+    /// interrupt enabled on both pins is reported on INTA.
+    pub(crate) fn from_registers(inta: InterruptReg, intb: InterruptReg) -> Self {
+        let route = |enabled: fn(&InterruptReg) -> bool| {
+            if enabled(&inta) {
+                Some(InterruptPin::IntA)
+            } else if enabled(&intb) {
+                Some(InterruptPin::IntB)
+            } else {
+                None
+            }
+        };
+        let signal = |int: InterruptReg| {
+            if int.level() {
+                Signal::Level
+            } else {
+                Signal::Pulse
+            }
+        };
+
+        InterruptRouting {
+            periodic: route(InterruptReg::periodic_interrupt),
+            offset_correction: route(InterruptReg::offset_correction_interrupt),
+            alarm1: route(InterruptReg::alarm1_interrupt),
+            alarm2: route(InterruptReg::alarm2_interrupt),
+            timestamp: route(InterruptReg::timestamp_interrupt),
+            battery_switch: route(InterruptReg::battery_switch_interrupt),
+            watchdog: route(InterruptReg::watchdog_interrupt),
+            inta_signal: signal(inta),
+            intb_signal: signal(intb),
+        }
+    }
+}
+
+/// Configuration of the whole device.
+///
+/// The default matches the power-on state of the chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub load_capacitance: LoadCapacitance,
+    pub crystal_drive: CrystalDrive,
+    pub low_jitter: bool,
+    pub offset_mode: OffsetMode,
+    /// Frequency offset correction in ppb.
+    ///
+    /// The offset register holds steps of about 2 ppm, so the value read
+    /// back is the correction actually applied.
+    pub offset_ppb: i32,
+    pub clock_output: ClockOutputFrequency,
+    pub inverted_clockout: bool,
+    pub periodic_interrupt: PeriodicInterrupt,
+    pub inta_pin: IntAPinMode,
+    /// [`TsPinMode::Input`] keeps the input settings made with
+    /// [`Pcf85263a::configure_ts_input`]
+    pub ts_pin: TsPinMode,
+    /// Clock output on the CLK pin
+    pub clk_pin: bool,
+    pub interrupts: InterruptRouting,
+    /// Switch-over to the battery, it only happens if enabled
+    pub switch_over: bool,
+    pub switch_over_mode: BatterySwitchMode,
+    pub switch_over_threshold: BatterySwitchThreshold,
+    /// Samples the supply voltage more often, at the cost of current
+    pub high_refresh_rate: bool,
+    /// Watchdog configuration, `None` disables the watchdog
+    pub watchdog: Option<WatchdogConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            load_capacitance: LoadCapacitance::Cl7pF,
+            crystal_drive: CrystalDrive::Normal,
+            low_jitter: false,
+            offset_mode: OffsetMode::Normal,
+            offset_ppb: 0,
+            clock_output: ClockOutputFrequency::F32768,
+            inverted_clockout: false,
+            periodic_interrupt: PeriodicInterrupt::NoInterrupt,
+            inta_pin: IntAPinMode::ClkOutputMode,
+            ts_pin: TsPinMode::Disabled,
+            clk_pin: true,
+            interrupts: InterruptRouting::default(),
+            switch_over: true,
+            switch_over_mode: BatterySwitchMode::Threshold,
+            switch_over_threshold: BatterySwitchThreshold::V1_5,
+            high_refresh_rate: false,
+            watchdog: None,
+        }
+    }
+}
+
+impl Config {
+    pub(crate) fn oscillator_register(&self, osc: OscillatorReg) -> OscillatorReg {
+        osc.with_load_capacitance(self.load_capacitance)
+            .with_crystal_drive(self.crystal_drive)
+            .with_low_jitter(self.low_jitter)
+            .with_offset_mode(self.offset_mode)
+            .with_inverted_clockout(self.inverted_clockout)
+    }
+
+    pub(crate) fn function_register(&self, function: FunctionReg) -> FunctionReg {
+        function
+            .with_clock_output_frequency(self.clock_output)
+            .with_periodic_interrupt(self.periodic_interrupt)
+    }
+
+    pub(crate) fn pinio_register(&self, pinio: PinIoReg) -> PinIoReg {
+        pinio
+            .with_inta_pinmode(self.inta_pin)
+            .with_ts_pinmode(self.ts_pin)
+            .with_clk_pin(self.clk_pin)
+    }
+
+    pub(crate) fn battery_switch_register(&self) -> BatterySwitchReg {
+        BatterySwitchReg::default()
+            .with_switch_over(self.switch_over)
+            .with_mode(self.switch_over_mode)
+            .with_threshold(self.switch_over_threshold)
+            .with_high_refresh_rate(self.high_refresh_rate)
+    }
+
+    pub(crate) fn offset_value<E>(&self) -> Result<i8, Error<E>> {
+        frequency_offset_value(self.offset_ppb, self.offset_mode)
+    }
+
+    pub(crate) fn watchdog_register<E>(&self) -> Result<WatchdogReg, Error<E>> {
+        match self.watchdog {
+            Some(wd) => wd.register().ok_or(Error::InvalidWatchdogTimeout),
+            None => Ok(WatchdogReg::default()),
+        }
+    }
+
+    /// Reconstructs the configuration from the registers `OFFSET` to
+    /// `INTB_ENABLE` and the watchdog register
+    pub(crate) fn from_registers(values: [u8; 7], watchdog: WatchdogReg) -> Self {
+        let [offset, osc, battery_switch, pin_io, function, inta, intb] = values;
+        let osc = OscillatorReg(osc);
+        let function = FunctionReg(function);
+        let pin_io = PinIoReg(pin_io);
+        let battery_switch = BatterySwitchReg(battery_switch);
+
+        Config {
+            load_capacitance: osc.load_capacitance(),
+            crystal_drive: osc.crystal_drive(),
+            low_jitter: osc.is_low_jitter(),
+            offset_mode: osc.offset_mode(),
+            offset_ppb: ppb_offset_for_offset_value(offset as i8, osc.offset_mode()),
+            clock_output: function.clock_output_frequency(),
+            inverted_clockout: osc.is_clockout_inverted(),
+            periodic_interrupt: function.periodic_interrupt(),
+            inta_pin: pin_io.inta_pinmode(),
+            ts_pin: pin_io.ts_pinmode(),
+            clk_pin: pin_io.is_clk_pin_enabled(),
+            interrupts: InterruptRouting::from_registers(InterruptReg(inta), InterruptReg(intb)),
+            switch_over: battery_switch.is_switch_over_enabled(),
+            switch_over_mode: battery_switch.mode(),
+            switch_over_threshold: battery_switch.threshold(),
+            high_refresh_rate: battery_switch.is_high_refresh_rate(),
+            watchdog: WatchdogConfig::from_register(watchdog),
+        }
+    }
+}
+
+/// Implements the configuration methods of `$driver`, see `impl_register_access`
+macro_rules! impl_config {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Writes the whole configuration.
+            ///
+            /// The oscillator goes first, as the offset depends on its offset mode,
+            /// and the clock output frequency is set before the pin modes. Interrupt
+            /// enables and the watchdog are written last, so nothing fires before
+            /// the rest is in place.
+            ///
+            /// `OFFSET`, `BATTERY_SWITCH`, both interrupt enable registers and
+            /// `WATCHDOG` are overwritten. The other registers are read-modify-written
+            /// and keep the bits the configuration doesn't cover:
+            ///
+            /// - `OSCILLATOR`: 12/24h mode
+            /// - `FUNCTION`: 100th seconds and stop-watch mode
+            /// - `PIN_IO`: the TS input level, type and pull-up of
+            ///   [`Pcf85263a::configure_ts_input`]
+            ///
+            /// Timestamp modes aren't part of the configuration either.
+            ///
+            /// Nothing is written if the offset or the watchdog timeout can't be
+            /// represented.
+            pub $($async)? fn apply_config(&mut self, config: &Config) -> Result<(), Error<E>> {
+                let offset = config.offset_value()?;
+                let watchdog = config.watchdog_register()?;
+
+                let osc = self.read_oscillator_register()$($await)*?;
+                self.write_oscillator_register(config.oscillator_register(osc))$($await)*?;
+                self.write_offset_register(offset)$($await)*?;
+                self.write_battery_switch_register(config.battery_switch_register())$($await)*?;
+
+                let function = self.read_function_register()$($await)*?;
+                self.write_function_register(config.function_register(function))$($await)*?;
+                let pinio = self.read_pinio_register()$($await)*?;
+                self.write_pinio_register(config.pinio_register(pinio))$($await)*?;
+
+                self.write_inta_register(config.interrupts.register(InterruptPin::IntA))$($await)*?;
+                self.write_intb_register(config.interrupts.register(InterruptPin::IntB))$($await)*?;
+                self.write_watchdog_register(watchdog)$($await)*
+            }
+
+            pub $($async)? fn read_config(&mut self) -> Result<Config, Error<E>> {
+                let values: [u8; 7] = self.read_register_multiple(Register::OFFSET)$($await)*?;
+                let watchdog = self.read_watchdog_register()$($await)*?;
+
+                self.clock_12h = Some(OscillatorReg(values[1]).is_12h_clock());
+                self.s100th = Some(FunctionReg(values[4]).s100th_enabled());
+
+                Ok(Config::from_registers(values, watchdog))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_config;
+
+impl_config!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use core::time::Duration;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    fn config() -> Config {
+        Config {
+            load_capacitance: LoadCapacitance::Cl12_5pF,
+            offset_mode: OffsetMode::Fast,
+            offset_ppb: 4_069,
+            clock_output: ClockOutputFrequency::StaticLow,
+            periodic_interrupt: PeriodicInterrupt::OncePerSecond,
+            inta_pin: IntAPinMode::IntA,
+            ts_pin: TsPinMode::IntB,
+            interrupts: InterruptRouting {
+                periodic: Some(InterruptPin::IntA),
+                alarm1: Some(InterruptPin::IntA),
+                watchdog: Some(InterruptPin::IntB),
+                intb_signal: Signal::Level,
+                ..Default::default()
+            },
+            switch_over_mode: BatterySwitchMode::Higher,
+            watchdog: Some(WatchdogConfig {
+                timeout: Duration::from_secs(10),
+                repeat: false,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let expectations = [
+            // 12h mode is kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x20]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OSCILLATOR, 0x62]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OFFSET, 0x02]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::BATTERY_SWITCH, 0x04]),
+            // 100th seconds are kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x80]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0xA7]),
+            // TS input settings are kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x7C]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x76]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x50]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x81]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::WATCHDOG, 0x29]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.apply_config(&config()).unwrap();
+
+        let mut invalid = config();
+        invalid.watchdog = Some(WatchdogConfig {
+            timeout: Duration::from_millis(100),
+            repeat: false,
+        });
+        assert!(matches!(
+            rtc.apply_config(&invalid),
+            Err(Error::InvalidWatchdogTimeout)
+        ));
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_read_config() {
+        let expectations = [
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::OFFSET],
+                vec![0x02, 0x62, 0x04, 0x76, 0xA7, 0x50, 0x81],
+            ),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::WATCHDOG], vec![0x29]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OFFSET], vec![0x00; 7]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::WATCHDOG], vec![0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        assert_eq!(rtc.read_config().unwrap(), config());
+        assert_eq!(rtc.read_config().unwrap(), Config::default());
+
+        rtc.release().release().done();
+    }
+}
@@ -1,6 +1,7 @@
-use crate::register_access::{OscillatorReg, Register, RegisterAccess};
+use crate::register_access::{Register, RegisterAccess};
 use crate::{Error, Pcf85263a};
 
+use chrono::Weekday;
 use rtcc::{DateTimeAccess, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 
 impl<I, E> DateTimeAccess for Pcf85263a<I>
@@ -22,87 +23,292 @@ impl<I, E> Pcf85263a<I>
 where
     I: RegisterAccess<Error = E>,
 {
-    pub fn time(&mut self) -> Result<NaiveTime, Error<E>> {
-        let [seconds_100th, seconds, minutes, hours] =
-            self.read_register_multiple(Register::SECONDS_100TH)?;
-        let osc_reg = self.read_oscillator_register()?; // TODO should probably get rid of this..
-
-        let hour = decode_hours(hours, osc_reg).as_24h().into();
-        let minute = decode_minutes(minutes).into();
-        let second = decode_seconds(seconds).into();
-        let millisecond = (decode_seconds_100th(seconds_100th) as u32 * 10).min(999);
+    /// Sets date and time, including the weekday, while the clock is stopped.
+    ///
+    /// All eight time registers go out in one
+    /// [`RegisterAccess::write_registers`] call, i.e. a single I2C write.
+    pub fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Error<E>> {
+        self.set_datetime_on_pps(datetime, || ())
+    }
 
-        Ok(NaiveTime::from_hms_milli_opt(hour, minute, second, millisecond).unwrap())
+    /// Like [`Pcf85263a::set_datetime`], but starts the clock on an external
+    /// 1PPS edge.
+    ///
+    /// The registers are written while the clock is stopped, then
+    /// `wait_for_pps` is called and the clock is started as soon as it
+    /// returns. It should block until the edge, and `datetime` should be the
+    /// time of that edge.
+    pub fn set_datetime_on_pps<F: FnOnce()>(
+        &mut self,
+        datetime: &NaiveDateTime,
+        wait_for_pps: F,
+    ) -> Result<(), Error<E>> {
+        self.write_datetime_stopped(datetime)?;
+        wait_for_pps();
+        self.write_stop_register(false)
     }
+}
 
-    pub fn set_time(&mut self, time: NaiveTime) -> Result<(), Error<E>> {
-        let osc_reg = self.read_oscillator_register()?;
-        // see datasheet page 14
-        self.write_stop_register(true)?;
-        self.clear_prescaler()?;
-        self.write_register_multiple(
-            Register::SECONDS_100TH,
-            &[
-                0,
-                encode_bcd(time.second() as u8),
-                encode_bcd(time.minute() as u8),
-                encode_hours(time.hour() as u8, osc_reg),
-            ],
-        )?;
-        self.write_stop_register(false)?;
+/// Implements the date and time methods of `$driver`, see `impl_register_access`
+macro_rules! impl_datetime {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Enables or disables the 100th seconds counter.
+            ///
+            /// When enabled, the time is read and set with a resolution of 10 ms,
+            /// otherwise in whole seconds.
+            pub $($async)? fn set_100th_mode(&mut self, enable: bool) -> Result<(), Error<E>> {
+                let function = self.read_function_register()$($await)*?;
+
+                self.write_function_register(function.with_100th(enable))$($await)*
+            }
 
-        Ok(())
-    }
+            pub $($async)? fn time(&mut self) -> Result<NaiveTime, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let s100th = self.is_100th_mode()$($await)*?;
+                let values = self.read_register_multiple(Register::SECONDS_100TH)$($await)*?;
 
-    pub fn date(&mut self) -> Result<NaiveDate, Error<E>> {
-        let [days, _weekdays, months, years] = self.read_register_multiple(Register::DAYS)?;
+                decode_time(values, clock_12h, s100th).ok_or(Error::InvalidDate)
+            }
 
-        Ok(NaiveDate::from_ymd_opt(
-            decode_years(years).into(),
-            decode_months(months).into(),
-            decode_days(days).into(),
-        )
-        .unwrap())
-    }
+            pub $($async)? fn set_time(&mut self, time: NaiveTime) -> Result<(), Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let s100th = self.is_100th_mode()$($await)*?;
+                // see datasheet page 14
+                self.write_stop_register(true)$($await)*?;
+                self.clear_prescaler()$($await)*?;
+                self.write_register_multiple(
+                    Register::SECONDS_100TH,
+                    &encode_time(time, clock_12h, s100th),
+                )
+                $($await)*?;
+                self.write_stop_register(false)$($await)*?;
+
+                Ok(())
+            }
 
-    pub fn set_date(&mut self, date: NaiveDate) -> Result<(), Error<E>> {
-        self.write_stop_register(true)?;
+            pub $($async)? fn date(&mut self) -> Result<NaiveDate, Error<E>> {
+                let values = self.read_register_multiple(Register::DAYS)$($await)*?;
 
-        self.write_register(Register::DAYS, encode_bcd(date.day() as u8))?;
-        self.write_register(Register::MONTHS, encode_bcd(date.month() as u8))?;
-        self.write_register(Register::YEARS, encode_years(date.year())?)?;
+                decode_date(values).ok_or(Error::InvalidDate)
+            }
 
-        self.write_stop_register(false)?;
+            pub $($async)? fn set_date(&mut self, date: NaiveDate) -> Result<(), Error<E>> {
+                let values = encode_date(date)?;
 
-        Ok(())
-    }
+                self.write_stop_register(true)$($await)*?;
+                self.write_register_multiple(Register::DAYS, &values)$($await)*?;
+                self.write_stop_register(false)$($await)*?;
 
-    pub fn datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
-        Ok(self.date()?.and_time(self.time()?))
-    }
+                Ok(())
+            }
 
-    pub fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Error<E>> {
-        self.set_date(datetime.date())?;
-        self.set_time(datetime.time())?;
+            /// Reads date and time in a single burst, so they can't be torn apart by
+            /// a rollover between two reads.
+            ///
+            /// The 12/24h and 100th seconds modes live in separate registers, so the
+            /// first call reads those as well. They are cached afterwards, see
+            /// [`Pcf85263a::invalidate_cache`].
+            pub $($async)? fn datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let s100th = self.is_100th_mode()$($await)*?;
+                let values = self.read_register_multiple(Register::SECONDS_100TH)$($await)*?;
+
+                decode_full_datetime(values, clock_12h, s100th).ok_or(Error::InvalidDate)
+            }
+
+            /// Stops the clock and writes `datetime`, leaving the clock stopped.
+            /// Nothing is written if it can't be encoded.
+            pub(crate) $($async)? fn write_datetime_stopped(
+                &mut self,
+                datetime: &NaiveDateTime,
+            ) -> Result<(), Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let s100th = self.is_100th_mode()$($await)*?;
+                let values = encode_full_datetime(datetime, clock_12h, s100th)?;
+
+                // see datasheet page 14
+                self.write_stop_register(true)$($await)*?;
+                self.clear_prescaler()$($await)*?;
+                self.write_register_multiple(Register::SECONDS_100TH, &values)$($await)*
+            }
+
+            /// Like [`Pcf85263a::datetime`], but returns [`Error::ClockIntegrityLost`]
+            /// if the oscillator stopped since the time was last set, e.g. because
+            /// the backup battery ran empty.
+            pub $($async)? fn checked_datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let s100th = self.is_100th_mode()$($await)*?;
+                let values = self.read_register_multiple(Register::SECONDS_100TH)$($await)*?;
+
+                decode_checked_datetime(values, clock_12h, s100th)
+            }
+
+            /// Returns `true` if the oscillator stopped and the time is no longer reliable
+            pub $($async)? fn oscillator_stopped(&mut self) -> Result<bool, Error<E>> {
+                Ok(self.read_register(Register::SECONDS)$($await)*? & OS_FLAG > 0)
+            }
 
-        Ok(())
+            /// Clears the oscillator stop flag. Setting the time clears it as well.
+            ///
+            /// The flag shares the seconds register with the time, so the clock is
+            /// stopped while it's rewritten, or a tick in between would get lost.
+            /// Like setting the time, this clears the prescaler, so the clock falls
+            /// behind by up to a second.
+            pub $($async)? fn clear_oscillator_stop_flag(&mut self) -> Result<(), Error<E>> {
+                // see datasheet page 14
+                self.write_stop_register(true)$($await)*?;
+                self.clear_prescaler()$($await)*?;
+
+                let seconds = self.read_register(Register::SECONDS)$($await)*?;
+                self.write_register(Register::SECONDS, seconds & !OS_FLAG)$($await)*?;
+                self.write_stop_register(false)$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_datetime;
+
+impl_datetime!(Pcf85263a, RegisterAccess, [], []);
+
+/// Like [`decode_full_datetime`], but fails with [`Error::ClockIntegrityLost`]
+/// if the oscillator stop flag is set
+pub(crate) fn decode_checked_datetime<E>(
+    values: [u8; 8],
+    clock_12h: bool,
+    s100th: bool,
+) -> Result<NaiveDateTime, Error<E>> {
+    if values[1] & OS_FLAG > 0 {
+        return Err(Error::ClockIntegrityLost);
     }
+
+    decode_full_datetime(values, clock_12h, s100th).ok_or(Error::InvalidDate)
 }
 
-fn decode_seconds(val: u8) -> u8 {
+/// Decodes the registers `SECONDS_100TH` to `HOURS`. The 100th seconds are
+/// ignored unless `s100th` is set.
+pub(crate) fn decode_time(values: [u8; 4], clock_12h: bool, s100th: bool) -> Option<NaiveTime> {
+    let [seconds_100th, seconds, minutes, hours] = values;
+
+    let hour = decode_hours(hours, clock_12h).as_24h().into();
+    let minute = decode_minutes(minutes).into();
+    let second = decode_seconds(seconds).into();
+    let millisecond = if s100th {
+        (decode_seconds_100th(seconds_100th) as u32 * 10).min(999)
+    } else {
+        0
+    };
+
+    NaiveTime::from_hms_milli_opt(hour, minute, second, millisecond)
+}
+
+/// Encodes the registers `SECONDS_100TH` to `HOURS`. With `s100th` set, the
+/// fraction is rounded to 10 ms without carrying over into the seconds.
+pub(crate) fn encode_time(time: NaiveTime, clock_12h: bool, s100th: bool) -> [u8; 4] {
+    let seconds_100th = if s100th {
+        ((time.nanosecond() + 5_000_000) / 10_000_000).min(99) as u8
+    } else {
+        0
+    };
+
+    [
+        encode_bcd(seconds_100th),
+        encode_bcd(time.second() as u8),
+        encode_bcd(time.minute() as u8),
+        encode_hours(time.hour() as u8, clock_12h),
+    ]
+}
+
+/// Decodes the registers `DAYS` to `YEARS`
+pub(crate) fn decode_date(values: [u8; 4]) -> Option<NaiveDate> {
+    let [days, _weekdays, months, years] = values;
+
+    NaiveDate::from_ymd_opt(
+        decode_years(years).into(),
+        decode_months(months).into(),
+        decode_days(days).into(),
+    )
+}
+
+/// Encodes the registers `DAYS` to `YEARS`
+pub(crate) fn encode_date<E>(date: NaiveDate) -> Result<[u8; 4], Error<E>> {
+    Ok([
+        encode_bcd(date.day() as u8),
+        encode_weekday(date.weekday()),
+        encode_bcd(date.month() as u8),
+        encode_years(date.year())?,
+    ])
+}
+
+/// Encodes the registers `SECONDS_100TH` to `YEARS`
+pub(crate) fn encode_full_datetime<E>(
+    datetime: &NaiveDateTime,
+    clock_12h: bool,
+    s100th: bool,
+) -> Result<[u8; 8], Error<E>> {
+    let [s100, seconds, minutes, hours] = encode_time(datetime.time(), clock_12h, s100th);
+    let [days, weekdays, months, years] = encode_date(datetime.date())?;
+
+    Ok([s100, seconds, minutes, hours, days, weekdays, months, years])
+}
+
+/// Decodes the registers `SECONDS_100TH` to `YEARS`
+pub(crate) fn decode_full_datetime(
+    values: [u8; 8],
+    clock_12h: bool,
+    s100th: bool,
+) -> Option<NaiveDateTime> {
+    let [s100, seconds, minutes, hours, days, weekdays, months, years] = values;
+
+    Some(
+        decode_date([days, weekdays, months, years])?.and_time(decode_time(
+            [s100, seconds, minutes, hours],
+            clock_12h,
+            s100th,
+        )?),
+    )
+}
+
+/// Decodes seconds, minutes, hours, days, months and years as stored in the
+/// timestamp registers. Returns `None` if the values don't form a valid date,
+/// e.g. for cleared registers.
+pub(crate) fn decode_datetime(values: [u8; 6], clock_12h: bool) -> Option<NaiveDateTime> {
+    let [seconds, minutes, hours, days, months, years] = values;
+
+    NaiveDate::from_ymd_opt(
+        decode_years(years).into(),
+        decode_months(months).into(),
+        decode_days(days).into(),
+    )?
+    .and_hms_opt(
+        decode_hours(hours, clock_12h).as_24h().into(),
+        decode_minutes(minutes).into(),
+        decode_seconds(seconds).into(),
+    )
+}
+
+/// Oscillator stop flag in the seconds register
+pub(crate) const OS_FLAG: u8 = 1 << 7;
+
+pub(crate) fn decode_seconds(val: u8) -> u8 {
     decode_bcd(val & 0b01111111)
 }
 
-fn decode_minutes(val: u8) -> u8 {
+pub(crate) fn decode_minutes(val: u8) -> u8 {
     decode_bcd(val & 0b01111111)
 }
 
-fn decode_seconds_100th(val: u8) -> u8 {
+pub(crate) fn decode_seconds_100th(val: u8) -> u8 {
     decode_bcd(val)
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Hours {
+pub(crate) enum Hours {
     AM(u8),
     PM(u8),
     H24(u8),
@@ -147,26 +353,29 @@ impl Hours {
     }
 }
 
-fn decode_hours(hours: u8, osc_reg: OscillatorReg) -> Hours {
-    if osc_reg.is_12h_clock() {
+/// AM/PM indicator in 12h mode, set for PM
+const AMPM: u8 = 1 << 5;
+
+pub(crate) fn decode_hours(hours: u8, clock_12h: bool) -> Hours {
+    if clock_12h {
         let h12_hour = decode_bcd(hours & 0b00011111);
-        if hours & (1 << 5) > 0 {
-            Hours::AM(h12_hour)
-        } else {
+        if hours & AMPM > 0 {
             Hours::PM(h12_hour)
+        } else {
+            Hours::AM(h12_hour)
         }
     } else {
         Hours::H24(decode_bcd(hours & 0b00111111))
     }
 }
 
-fn encode_hours(hours: u8, osc_reg: OscillatorReg) -> u8 {
-    if osc_reg.is_12h_clock() {
+pub(crate) fn encode_hours(hours: u8, clock_12h: bool) -> u8 {
+    if clock_12h {
         let hours = Hours::from_24h_as_ampm(hours);
 
         match hours {
-            Hours::AM(am) => encode_bcd(am) | (1 << 5),
-            Hours::PM(pm) => encode_bcd(pm),
+            Hours::AM(am) => encode_bcd(am),
+            Hours::PM(pm) => encode_bcd(pm) | AMPM,
             _ => unreachable!(),
         }
     } else {
@@ -174,35 +383,45 @@ fn encode_hours(hours: u8, osc_reg: OscillatorReg) -> u8 {
     }
 }
 
-fn encode_years<E>(year: i32) -> Result<u8, Error<E>> {
-    if year < 2000 || year >= 3000 {
+pub(crate) fn encode_years<E>(year: i32) -> Result<u8, Error<E>> {
+    if !(2000..2100).contains(&year) {
         Err(Error::InvalidDate)
     } else {
-        let year = (2000 - year) as u8;
+        let year = (year - 2000) as u8;
         Ok(encode_bcd(year))
     }
 }
 
-fn decode_days(days: u8) -> u8 {
+pub(crate) fn decode_days(days: u8) -> u8 {
     decode_bcd(days & 0b00111111)
 }
 
-fn decode_months(months: u8) -> u8 {
-    decode_bcd(months & 0b00000111)
+pub(crate) fn decode_months(months: u8) -> u8 {
+    decode_bcd(months & 0b00011111)
+}
+
+/// Weekdays are counted from 0 (Sunday) to 6 (Saturday)
+pub(crate) fn encode_weekday(weekday: Weekday) -> u8 {
+    weekday.num_days_from_sunday() as u8
+}
+
+pub(crate) fn decode_weekday(weekday: u8) -> Weekday {
+    // chrono counts from monday
+    Weekday::try_from(((weekday & 0b111) + 6) % 7).unwrap_or(Weekday::Sun)
 }
 
 fn decode_years(years: u8) -> u16 {
     decode_bcd(years) as u16 + 2000
 }
 
-fn decode_bcd(bcd: u8) -> u8 {
+pub(crate) fn decode_bcd(bcd: u8) -> u8 {
     let unit = bcd & 0xF;
     let tens = (bcd >> 4) & 0xF;
 
     unit + tens * 10
 }
 
-fn encode_bcd(val: u8) -> u8 {
+pub(crate) fn encode_bcd(val: u8) -> u8 {
     let unit = val % 10;
     let tens = val / 10;
 
@@ -212,6 +431,8 @@ fn encode_bcd(val: u8) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
 
     #[test]
     fn test_decode_bcd() {
@@ -227,6 +448,16 @@ mod tests {
         assert_eq!(encode_bcd(98), 0b10011000);
     }
 
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(encode_weekday(Weekday::Sun), 0);
+        assert_eq!(encode_weekday(Weekday::Sat), 6);
+
+        for day in 0..=6 {
+            assert_eq!(encode_weekday(decode_weekday(day)), day);
+        }
+    }
+
     #[test]
     fn test_hours_to_24h() {
         for h in 0..=23 {
@@ -243,4 +474,183 @@ mod tests {
             assert_eq!(Hours::PM(h).as_24h(), h + 12);
         }
     }
+
+    #[test]
+    fn test_hours_12h() {
+        // 12h mode as in the datasheet hours register, AMPM set for PM
+        assert_eq!(encode_hours(0, true), 0x12);
+        assert_eq!(encode_hours(7, true), 0x07);
+        assert_eq!(encode_hours(12, true), 0x32);
+        assert_eq!(encode_hours(23, true), 0x31);
+
+        assert_eq!(decode_hours(0x12, true), Hours::AM(12));
+        assert_eq!(decode_hours(0x07, true), Hours::AM(7));
+        assert_eq!(decode_hours(0x32, true), Hours::PM(12));
+        assert_eq!(decode_hours(0x31, true), Hours::PM(11));
+
+        assert_eq!(encode_hours(23, false), 0x23);
+        assert_eq!(decode_hours(0x23, false), Hours::H24(23));
+    }
+
+    #[test]
+    fn test_encode_years() {
+        assert_eq!(encode_years::<()>(2000).unwrap(), 0x00);
+        assert_eq!(encode_years::<()>(2026).unwrap(), 0x26);
+        assert_eq!(encode_years::<()>(2099).unwrap(), 0x99);
+        assert!(encode_years::<()>(1999).is_err());
+        assert!(encode_years::<()>(2100).is_err());
+    }
+
+    #[test]
+    fn test_encode_time_100th() {
+        let time = NaiveTime::from_hms_milli_opt(12, 34, 56, 125).unwrap();
+        assert_eq!(encode_time(time, false, true), [0x13, 0x56, 0x34, 0x12]);
+        assert_eq!(encode_time(time, false, false), [0x00, 0x56, 0x34, 0x12]);
+
+        // no carry into the seconds
+        let time = NaiveTime::from_hms_milli_opt(12, 34, 56, 996).unwrap();
+        assert_eq!(encode_time(time, false, true), [0x99, 0x56, 0x34, 0x12]);
+
+        assert_eq!(
+            decode_time([0x42, 0x56, 0x34, 0x12], false, true),
+            NaiveTime::from_hms_milli_opt(12, 34, 56, 420)
+        );
+        assert_eq!(
+            decode_time([0x42, 0x56, 0x34, 0x12], false, false),
+            NaiveTime::from_hms_opt(12, 34, 56)
+        );
+    }
+
+    #[test]
+    fn test_set_datetime() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0xA4]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00]),
+            I2cTransaction::write(
+                DEFAULT_ADDRESS,
+                vec![0x00, 0x56, 0x34, 0x12, 0x16, 0x05, 0x10, 0x26],
+            ),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        // a friday
+        let datetime = NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_opt(12, 34, 56)
+            .unwrap();
+        rtc.set_datetime(&datetime).unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_set_datetime_on_pps() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x80]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0xA4]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00]),
+            I2cTransaction::write(
+                DEFAULT_ADDRESS,
+                vec![0x50, 0x56, 0x34, 0x12, 0x16, 0x05, 0x10, 0x26],
+            ),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.set_100th_mode(true).unwrap();
+
+        let datetime = NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_milli_opt(12, 34, 56, 500)
+            .unwrap();
+        let mut waited = false;
+        rtc.set_datetime_on_pps(&datetime, || waited = true)
+            .unwrap();
+        assert!(waited);
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_datetime_single_read() {
+        let values = vec![0x99, 0x59, 0x59, 0x23, 0x31, 0x05, 0x12, 0x25];
+        let expectations = [
+            // cold: 12/24h and 100th mode are read before the burst
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x80]),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::SECONDS_100TH],
+                values.clone(),
+            ),
+            // warm: only the burst read is left
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::SECONDS_100TH],
+                values.clone(),
+            ),
+            // invalidated: the modes are read again
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x80]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::SECONDS_100TH], values),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let expected = NaiveDate::from_ymd_opt(2025, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 990)
+            .unwrap();
+        assert_eq!(rtc.datetime().unwrap(), expected);
+        assert_eq!(rtc.datetime().unwrap(), expected);
+        rtc.invalidate_cache();
+        assert_eq!(rtc.datetime().unwrap(), expected);
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_oscillator_stop() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::SECONDS_100TH],
+                vec![0x00, 0x80, 0x00, 0x00, 0x01, 0x06, 0x01, 0x00],
+            ),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0xA4]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::SECONDS], vec![0x80 | 0x42]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::SECONDS, 0x42]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        assert!(matches!(
+            rtc.checked_datetime(),
+            Err(Error::ClockIntegrityLost)
+        ));
+        rtc.clear_oscillator_stop_flag().unwrap();
+
+        rtc.release().release().done();
+    }
 }
@@ -0,0 +1,393 @@
+use crate::register_access::{
+    Flags, IntAPinMode, InterruptPin, InterruptReg, PeriodicInterrupt, PinIoReg, RegisterAccess,
+    TsPinMode,
+};
+use crate::{Error, Pcf85263a, Signal, TsrSlot};
+
+/// Interrupt event, routed with [`Pcf85263a::route_interrupt`] and
+/// reported by [`Pcf85263a::service_interrupt`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Periodic,
+    /// Offset correction pulse. It has no flag, so it's never reported by
+    /// [`Pcf85263a::service_interrupt`].
+    OffsetCorrection,
+    Alarm1,
+    Alarm2,
+    Watchdog,
+    BatterySwitch,
+    /// Event captured by a timestamp register. The three registers share
+    /// a single interrupt enable, so routing one routes all of them.
+    Timestamp(TsrSlot),
+}
+
+impl Event {
+    pub const ALL: [Event; 9] = [
+        Event::Periodic,
+        Event::OffsetCorrection,
+        Event::Alarm1,
+        Event::Alarm2,
+        Event::Watchdog,
+        Event::BatterySwitch,
+        Event::Timestamp(TsrSlot::Tsr1),
+        Event::Timestamp(TsrSlot::Tsr2),
+        Event::Timestamp(TsrSlot::Tsr3),
+    ];
+
+    /// Returns the flag of the event, empty for [`Event::OffsetCorrection`]
+    pub fn flag(&self) -> Flags {
+        let flags = Flags::default();
+
+        match self {
+            Event::Periodic => flags.with_periodic(true),
+            Event::OffsetCorrection => flags,
+            Event::Alarm1 => flags.with_alarm1(true),
+            Event::Alarm2 => flags.with_alarm2(true),
+            Event::Watchdog => flags.with_watchdog(true),
+            Event::BatterySwitch => flags.with_battery_switch(true),
+            Event::Timestamp(TsrSlot::Tsr1) => flags.with_timestamp1(true),
+            Event::Timestamp(TsrSlot::Tsr2) => flags.with_timestamp2(true),
+            Event::Timestamp(TsrSlot::Tsr3) => flags.with_timestamp3(true),
+        }
+    }
+
+    /// Enable bit in the interrupt enable registers
+    pub(crate) fn bit(&self) -> u8 {
+        match self {
+            Event::Periodic => InterruptReg::PIE,
+            Event::OffsetCorrection => InterruptReg::OIE,
+            Event::Alarm1 => InterruptReg::A1IE,
+            Event::Alarm2 => InterruptReg::A2IE,
+            Event::Watchdog => InterruptReg::WDIE,
+            Event::BatterySwitch => InterruptReg::BSIE,
+            Event::Timestamp(_) => InterruptReg::TSRIE,
+        }
+    }
+}
+
+/// Events returned by [`Pcf85263a::service_interrupt`].
+///
+/// The offset correction interrupt has no flag, so it's never contained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventSet(pub(crate) Flags);
+
+impl EventSet {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, event: Event) -> bool {
+        self.0.as_u8() & event.flag().as_u8() > 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Event> + '_ {
+        Event::ALL.into_iter().filter(|&event| self.contains(event))
+    }
+
+    pub fn flags(&self) -> Flags {
+        self.0
+    }
+}
+
+/// Sets the periodic interrupt enable and, on `pin`, the signal type
+pub(crate) fn periodic_interrupt_reg(
+    int: InterruptReg,
+    own_pin: InterruptPin,
+    pin: InterruptPin,
+    signal: Signal,
+) -> InterruptReg {
+    let enabled = pin == own_pin;
+    let int = int.with_periodic_interrupt(enabled);
+
+    if enabled {
+        int.with_level(signal == Signal::Level)
+    } else {
+        int
+    }
+}
+
+/// Puts `pin` into interrupt output mode. Returns `None` if it's used for
+/// another function: clock output, battery mode indication in case of INTA,
+/// or TS input in case of INTB.
+pub(crate) fn interrupt_pinio(pinio: PinIoReg, pin: InterruptPin) -> Option<PinIoReg> {
+    match pin {
+        InterruptPin::IntA => match pinio.inta_pinmode() {
+            IntAPinMode::ClkOutputMode | IntAPinMode::BatteryModeIndication => None,
+            _ => Some(pinio.with_inta_pinmode(IntAPinMode::IntA)),
+        },
+        InterruptPin::IntB => match pinio.ts_pinmode() {
+            TsPinMode::ClkOutput | TsPinMode::Input => None,
+            _ => Some(pinio.with_ts_pinmode(TsPinMode::IntB)),
+        },
+    }
+}
+
+/// Implements the interrupt methods of `$driver`, see `impl_register_access`
+macro_rules! impl_interrupt {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Routes the interrupt of `event` to `pin` and disables it on the other pin.
+            ///
+            /// The pin is switched to interrupt output mode as well. INTB is output on
+            /// the TS pin.
+            ///
+            /// Returns [`Error::PinConflict`] without writing anything if the pin is
+            /// configured as clock output, for INTA if it indicates the battery mode,
+            /// and for INTB if the TS pin is used as input. A pin set up for another
+            /// function is never reconfigured implicitly. INTA powers up as
+            /// clock output, so switch it to [`IntAPinMode::IntA`](crate::IntAPinMode::IntA)
+            /// first, e.g. with [`PinIoReg::with_inta_pinmode`] and
+            /// [`Pcf85263a::write_pinio_register`].
+            pub $($async)? fn route_interrupt(
+                &mut self,
+                event: Event,
+                pin: InterruptPin,
+            ) -> Result<(), Error<E>> {
+                self.set_interrupt_output(pin)$($await)*?;
+                self.set_interrupt_enable(event.bit(), Some(pin))$($await)*
+            }
+
+            /// Switches `pin` to interrupt output mode, see
+            /// [`Pcf85263a::route_interrupt`]. Only writes the pin register if
+            /// it changes, and nothing on [`Error::PinConflict`].
+            pub(crate) $($async)? fn set_interrupt_output(
+                &mut self,
+                pin: InterruptPin,
+            ) -> Result<(), Error<E>> {
+                let pinio = self.read_pinio_register()$($await)*?;
+                let routed = interrupt_pinio(pinio, pin).ok_or(Error::PinConflict)?;
+
+                if routed != pinio {
+                    self.write_pinio_register(routed)$($await)*?;
+                }
+
+                Ok(())
+            }
+
+            /// Disables the interrupt of `event` on both pins. The pin modes are left
+            /// untouched.
+            pub $($async)? fn unroute_interrupt(&mut self, event: Event) -> Result<(), Error<E>> {
+                self.set_interrupt_enable(event.bit(), None)$($await)*
+            }
+
+            /// Enables the periodic interrupt on `pin`, setting the period, the
+            /// pin mode and the interrupt enables with read-modify-writes.
+            ///
+            /// The signal type applies to all interrupts on that pin. Returns
+            /// [`Error::PinConflict`] like [`Pcf85263a::route_interrupt`], without
+            /// writing anything. [`PeriodicInterrupt::NoInterrupt`] is the same as
+            /// [`Pcf85263a::disable_periodic_interrupt`], ignoring `pin` and `signal`.
+            pub $($async)? fn enable_periodic_interrupt(
+                &mut self,
+                period: PeriodicInterrupt,
+                pin: InterruptPin,
+                signal: Signal,
+            ) -> Result<(), Error<E>> {
+                if period == PeriodicInterrupt::NoInterrupt {
+                    return self.disable_periodic_interrupt()$($await)*;
+                }
+
+                let pinio = self.read_pinio_register()$($await)*?;
+                let routed = interrupt_pinio(pinio, pin).ok_or(Error::PinConflict)?;
+
+                let function = self.read_function_register()$($await)*?;
+                self.write_function_register(function.with_periodic_interrupt(period))$($await)*?;
+
+                if routed != pinio {
+                    self.write_pinio_register(routed)$($await)*?;
+                }
+
+                let inta = self.read_inta_register()$($await)*?;
+                self.write_inta_register(periodic_interrupt_reg(
+                    inta,
+                    InterruptPin::IntA,
+                    pin,
+                    signal,
+                ))$($await)*?;
+
+                let intb = self.read_intb_register()$($await)*?;
+                self.write_intb_register(periodic_interrupt_reg(
+                    intb,
+                    InterruptPin::IntB,
+                    pin,
+                    signal,
+                ))$($await)*
+            }
+
+            /// Stops the periodic interrupt and disables it on both pins. The pin
+            /// modes are left untouched.
+            pub $($async)? fn disable_periodic_interrupt(&mut self) -> Result<(), Error<E>> {
+                let function = self.read_function_register()$($await)*?;
+                self.write_function_register(
+                    function.with_periodic_interrupt(PeriodicInterrupt::NoInterrupt),
+                )$($await)*?;
+
+                self.unroute_interrupt(Event::Periodic)$($await)*
+            }
+
+            /// Reads the flags once and clears exactly the ones that were set, so
+            /// events arriving in between aren't lost.
+            pub $($async)? fn service_interrupt(&mut self) -> Result<EventSet, Error<E>> {
+                let flags = self.read_flags()$($await)*?;
+
+                if !flags.is_empty() {
+                    self.clear_flags(flags)$($await)*?;
+                }
+
+                Ok(EventSet(flags))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_interrupt;
+
+impl_interrupt!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_access::Register;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_interrupt_pinio() {
+        let pinio = PinIoReg::default();
+        assert_eq!(interrupt_pinio(pinio, InterruptPin::IntA), None);
+        assert_eq!(
+            interrupt_pinio(pinio, InterruptPin::IntB),
+            Some(PinIoReg(0x04))
+        );
+
+        let pinio = PinIoReg::default()
+            .with_inta_pinmode(IntAPinMode::HiZ)
+            .with_ts_pinmode(TsPinMode::Input);
+        assert_eq!(
+            interrupt_pinio(pinio, InterruptPin::IntA),
+            Some(PinIoReg(0x0E))
+        );
+        assert_eq!(interrupt_pinio(pinio, InterruptPin::IntB), None);
+
+        let pinio = PinIoReg::default().with_inta_pinmode(IntAPinMode::BatteryModeIndication);
+        assert_eq!(interrupt_pinio(pinio, InterruptPin::IntA), None);
+    }
+
+    #[test]
+    fn test_service_interrupt() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FLAGS], vec![0x22]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FLAGS, 0xDD]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FLAGS], vec![0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let events = rtc.service_interrupt().unwrap();
+        assert!(events.contains(Event::Alarm1));
+        assert!(events.contains(Event::Timestamp(TsrSlot::Tsr2)));
+        assert!(!events.contains(Event::Alarm2));
+
+        let mut iter = events.iter();
+        assert_eq!(iter.next(), Some(Event::Alarm1));
+        assert_eq!(iter.next(), Some(Event::Timestamp(TsrSlot::Tsr2)));
+        assert_eq!(iter.next(), None);
+
+        assert!(rtc.service_interrupt().unwrap().is_empty());
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_periodic_interrupt() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+            // 100th seconds and clock output frequency are kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x86]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0xA6]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x04]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0xC0]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x80]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x90]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x50]),
+            // TS pin used as input
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x0C]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0xA6]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x86]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x80]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x80]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x50]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x10]),
+            // NoInterrupt disables instead, without touching PIN_IO
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0xA6]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x86]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x80]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x80]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x50]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x10]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.enable_periodic_interrupt(
+            PeriodicInterrupt::OncePerSecond,
+            InterruptPin::IntB,
+            Signal::Pulse,
+        )
+        .unwrap();
+        assert!(matches!(
+            rtc.enable_periodic_interrupt(
+                PeriodicInterrupt::OncePerMinute,
+                InterruptPin::IntB,
+                Signal::Level,
+            ),
+            Err(Error::PinConflict)
+        ));
+        rtc.disable_periodic_interrupt().unwrap();
+        rtc.enable_periodic_interrupt(
+            PeriodicInterrupt::NoInterrupt,
+            InterruptPin::IntA,
+            Signal::Level,
+        )
+        .unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_route_interrupt() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x03]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x07]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x10]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x10]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x08]),
+            // clock output on INTA
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x10]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x08]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x08]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.route_interrupt(Event::Alarm2, InterruptPin::IntB)
+            .unwrap();
+        assert!(matches!(
+            rtc.route_interrupt(Event::Watchdog, InterruptPin::IntA),
+            Err(Error::PinConflict)
+        ));
+        rtc.unroute_interrupt(Event::Alarm1).unwrap();
+
+        rtc.release().release().done();
+    }
+}
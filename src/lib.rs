@@ -1,14 +1,37 @@
 #![cfg_attr(not(test), no_std)]
 
+mod alarm;
+#[cfg(feature = "async")]
+pub mod asynch;
+mod calibration;
+mod compensation;
+mod config;
 mod datetime;
+mod interrupt;
+mod ram;
 mod register_access;
-
+mod stopwatch;
+mod timestamp;
+mod watchdog;
+
+pub use alarm::{Alarm1, Alarm2, Weekdays};
+pub use calibration::{CalibrationSample, DriftCalibration};
+pub use chrono::Weekday;
+pub use compensation::{TemperatureCompensator, DEFAULT_COEFFICIENT_PPB};
+pub use config::{Config, InterruptRouting, Signal};
+pub use interrupt::{Event, EventSet};
+pub use ram::RamValue;
 pub use register_access::RegisterAccess;
 pub use rtcc::{DateTimeAccess, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+pub use stopwatch::Stopwatch;
+pub use timestamp::{TamperEvent, TimestampMode, TsInputConfig, TsrSlot};
+pub use watchdog::WatchdogConfig;
 
 pub use crate::register_access::{
-    ClockOutputFrequency, CrystalDrive, FunctionReg, I2cInterface, IntAPinMode, InterruptReg,
-    LoadCapacitance, OscillatorReg, PeriodicInterrupt, PinIoReg,
+    BatterySwitchMode, BatterySwitchReg, BatterySwitchThreshold, ClockOutputFrequency,
+    CrystalDrive, Flags, FunctionReg, I2cInterface, IntAPinMode, InterruptPin, InterruptReg,
+    LoadCapacitance, OscillatorReg, PeriodicInterrupt, PinIoReg, ResetCommand, TsInputType,
+    TsLevel, TsPinMode, TsPullUp, WatchdogReg, WatchdogStepSize,
 };
 
 pub const DEFAULT_ADDRESS: u8 = 0x51; // 0xA2 (W) + 0xA3 (R)
@@ -17,9 +40,27 @@ pub const DEFAULT_ADDRESS: u8 = 0x51; // 0xA2 (W) + 0xA3 (R)
 pub enum Error<E> {
     Interface(E),
     InvalidDate,
+    /// The alarm can't be represented by the alarm registers
+    InvalidAlarm,
+    /// The timestamp register doesn't support the requested mode
+    InvalidTimestampMode,
+    /// The watchdog timeout isn't a whole number of steps of any step size
+    InvalidWatchdogTimeout,
+    /// The RAM byte doesn't hold a valid value, e.g. after a brown-out
+    RamCorrupted,
+    /// The oscillator has stopped since the flag was last cleared, so the time can't be trusted
+    ClockIntegrityLost,
+    /// The readback after a factory reset didn't match the power-on defaults
+    ResetFailed,
+    /// The requested frequency offset is outside of what the offset register can correct
+    InvalidOffset,
+    /// The reference time doesn't advance between the calibration samples
+    InvalidCalibration,
+    /// The pin is already used for another function, e.g. as clock output
+    PinConflict,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OffsetMode {
     /// Correction made every 4 hours, 2.170ppm/step
     Normal,
@@ -37,22 +78,56 @@ impl OffsetMode {
     }
 }
 
+/// Rounds `offset_ppb` to the nearest number of offset steps, which may be
+/// outside of the range of the offset register.
+fn offset_steps_for_ppb_offset(offset_ppb: i32, offset_mode: OffsetMode) -> i64 {
+    let tenthppb_per_pulse = offset_mode.offset_per_step() as i64;
+
+    ((offset_ppb as i64) * 10 + (offset_ppb.signum() as i64 * tenthppb_per_pulse / 2))
+        / tenthppb_per_pulse
+}
+
 /// Helper function to calculate the offset value for a given offset in ppb.
 ///
 /// This essentially maps Table 24 from the datasheet.
 pub fn offset_value_for_ppb_offset(offset_ppb: i32, offset_mode: OffsetMode) -> i8 {
-    let tenthppb_per_pulse: i64 = match offset_mode {
-        OffsetMode::Normal => 21700,
-        OffsetMode::Fast => 20345,
-    };
-
-    (((offset_ppb as i64) * 10 + (offset_ppb.signum() as i64 * tenthppb_per_pulse / 2))
-        / tenthppb_per_pulse)
-        .clamp(i8::MIN as i64, i8::MAX as i64) as i8
+    offset_steps_for_ppb_offset(offset_ppb, offset_mode).clamp(i8::MIN as i64, i8::MAX as i64) as i8
+}
+
+/// Like [`offset_value_for_ppb_offset`], but returns `None` instead of clamping
+/// if the offset is out of range.
+pub(crate) fn checked_offset_value_for_ppb_offset(
+    offset_ppb: i32,
+    offset_mode: OffsetMode,
+) -> Option<i8> {
+    i8::try_from(offset_steps_for_ppb_offset(offset_ppb, offset_mode)).ok()
+}
+
+/// Returns the offset register value for `offset_ppb`, or
+/// [`Error::InvalidOffset`] if it's out of range for `offset_mode`
+pub(crate) fn frequency_offset_value<E>(
+    offset_ppb: i32,
+    offset_mode: OffsetMode,
+) -> Result<i8, Error<E>> {
+    checked_offset_value_for_ppb_offset(offset_ppb, offset_mode).ok_or(Error::InvalidOffset)
+}
+
+/// Inverse of [`offset_value_for_ppb_offset`], returns the correction in ppb
+/// applied by an offset register value.
+pub fn ppb_offset_for_offset_value(offset: i8, offset_mode: OffsetMode) -> i32 {
+    let tenthppb = offset as i64 * offset_mode.offset_per_step() as i64;
+
+    ((tenthppb + tenthppb.signum() * 5) / 10) as i32
 }
 
 pub struct Pcf85263a<I> {
     interface: I,
+    /// 12/24h mode of the clock, read from the oscillator register on first
+    /// use. Cleared by `invalidate_cache`.
+    clock_12h: Option<bool>,
+    /// 100th seconds mode, read from the function register on first use.
+    /// Cleared by `invalidate_cache`.
+    s100th: Option<bool>,
 }
 
 impl<I, E> Pcf85263a<I>
@@ -60,7 +135,11 @@ where
     I: RegisterAccess<Error = E>,
 {
     pub fn new(interface: I) -> Self {
-        Pcf85263a { interface }
+        Pcf85263a {
+            interface,
+            clock_12h: None,
+            s100th: None,
+        }
     }
 
     pub fn release(self) -> I {
@@ -108,4 +187,47 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_checked_offsets() {
+        assert_eq!(
+            checked_offset_value_for_ppb_offset(5_000, OffsetMode::Normal),
+            Some(2)
+        );
+        assert_eq!(
+            checked_offset_value_for_ppb_offset(-275_600, OffsetMode::Normal),
+            Some(-127)
+        );
+        assert_eq!(
+            checked_offset_value_for_ppb_offset(258_000, OffsetMode::Fast),
+            Some(127)
+        );
+        assert_eq!(
+            checked_offset_value_for_ppb_offset(300_000, OffsetMode::Normal),
+            None
+        );
+        assert_eq!(
+            checked_offset_value_for_ppb_offset(-300_000, OffsetMode::Fast),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ppb_offsets() {
+        assert_eq!(ppb_offset_for_offset_value(0, OffsetMode::Normal), 0);
+        assert_eq!(ppb_offset_for_offset_value(2, OffsetMode::Normal), 4_340);
+        assert_eq!(ppb_offset_for_offset_value(1, OffsetMode::Fast), 2_035);
+        assert_eq!(ppb_offset_for_offset_value(-1, OffsetMode::Fast), -2_035);
+        assert_eq!(
+            ppb_offset_for_offset_value(-128, OffsetMode::Normal),
+            -277_760
+        );
+
+        for offset in i8::MIN..=i8::MAX {
+            for mode in [OffsetMode::Normal, OffsetMode::Fast] {
+                let ppb = ppb_offset_for_offset_value(offset, mode);
+                assert_eq!(offset_value_for_ppb_offset(ppb, mode), offset);
+            }
+        }
+    }
 }
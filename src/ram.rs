@@ -0,0 +1,142 @@
+use crate::register_access::{Register, RegisterAccess};
+use crate::{Error, Pcf85263a};
+
+/// A value that can be stored in the RAM byte alongside a checksum.
+///
+/// The value is packed into the lower nibble, the upper nibble holds its
+/// complement. Both all-zero and all-one bytes, as left behind by a power
+/// loss, fail the check.
+pub trait RamValue: Sized {
+    /// Packs the value into the lower 4 bits
+    fn to_nibble(&self) -> u8;
+
+    /// Unpacks the value, returns `None` if `nibble` doesn't represent a valid value
+    fn from_nibble(nibble: u8) -> Option<Self>;
+}
+
+pub(crate) fn pack_nibble(nibble: u8) -> u8 {
+    let nibble = nibble & 0x0F;
+
+    (!nibble << 4) | nibble
+}
+
+pub(crate) fn unpack_nibble(val: u8) -> Option<u8> {
+    let nibble = val & 0x0F;
+
+    (val == pack_nibble(nibble)).then_some(nibble)
+}
+
+/// Implements the RAM methods of `$driver`, see `impl_register_access`
+macro_rules! impl_ram {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            pub $($async)? fn read_ram(&mut self) -> Result<u8, Error<E>> {
+                self.read_register(Register::RAM_BYTE)$($await)*
+            }
+
+            pub $($async)? fn write_ram(&mut self, val: u8) -> Result<(), Error<E>> {
+                self.write_register(Register::RAM_BYTE, val)$($await)*
+            }
+
+            /// Reads a value stored with [`Pcf85263a::write_ram_value`].
+            ///
+            /// Returns [`Error::RamCorrupted`] if the checksum doesn't match or the
+            /// stored nibble isn't a valid value.
+            pub $($async)? fn read_ram_value<T: RamValue>(&mut self) -> Result<T, Error<E>> {
+                unpack_nibble(self.read_ram()$($await)*?)
+                    .and_then(T::from_nibble)
+                    .ok_or(Error::RamCorrupted)
+            }
+
+            pub $($async)? fn write_ram_value<T: RamValue>(
+                &mut self,
+                val: &T,
+            ) -> Result<(), Error<E>> {
+                self.write_ram(pack_nibble(val.to_nibble()))$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_ram;
+
+impl_ram!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[derive(Debug, PartialEq)]
+    enum BootReason {
+        PowerOn,
+        Watchdog,
+    }
+
+    impl RamValue for BootReason {
+        fn to_nibble(&self) -> u8 {
+            match self {
+                BootReason::PowerOn => 1,
+                BootReason::Watchdog => 2,
+            }
+        }
+
+        fn from_nibble(nibble: u8) -> Option<Self> {
+            match nibble {
+                1 => Some(BootReason::PowerOn),
+                2 => Some(BootReason::Watchdog),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_nibble() {
+        assert_eq!(pack_nibble(0x0), 0xF0);
+        assert_eq!(pack_nibble(0x5), 0xA5);
+        assert_eq!(pack_nibble(0xF), 0x0F);
+
+        for nibble in 0..=0xF {
+            assert_eq!(unpack_nibble(pack_nibble(nibble)), Some(nibble));
+        }
+
+        assert_eq!(unpack_nibble(0x00), None);
+        assert_eq!(unpack_nibble(0xFF), None);
+        assert_eq!(unpack_nibble(0xB5), None);
+    }
+
+    #[test]
+    fn test_ram_value() {
+        let expectations = [
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RAM_BYTE, 0xD2]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::RAM_BYTE], vec![0xD2]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::RAM_BYTE], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::RAM_BYTE], vec![0xC3]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.write_ram_value(&BootReason::Watchdog).unwrap();
+        assert_eq!(
+            rtc.read_ram_value::<BootReason>().unwrap(),
+            BootReason::Watchdog
+        );
+        assert!(matches!(
+            rtc.read_ram_value::<BootReason>(),
+            Err(Error::RamCorrupted)
+        ));
+        // valid checksum, but not a valid value
+        assert!(matches!(
+            rtc.read_ram_value::<BootReason>(),
+            Err(Error::RamCorrupted)
+        ));
+
+        rtc.release().release().done();
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{Error, OffsetMode, Pcf85263a};
+use core::time::Duration;
+
+use embedded_hal::i2c::Operation;
+
+use crate::{
+    frequency_offset_value, ppb_offset_for_offset_value, Error, Event, OffsetMode, Pcf85263a,
+};
 
 pub struct Register;
 #[allow(dead_code)]
@@ -12,6 +18,21 @@ impl Register {
     pub const MONTHS: u8 = 0x06;
     pub const YEARS: u8 = 0x07;
 
+    pub const SECOND_ALARM1: u8 = 0x08;
+    pub const MINUTE_ALARM1: u8 = 0x09;
+    pub const HOUR_ALARM1: u8 = 0x0A;
+    pub const DAY_ALARM1: u8 = 0x0B;
+    pub const MONTH_ALARM1: u8 = 0x0C;
+    pub const MINUTE_ALARM2: u8 = 0x0D;
+    pub const HOUR_ALARM2: u8 = 0x0E;
+    pub const WEEKDAY_ALARM2: u8 = 0x0F;
+    pub const ALARM_ENABLES: u8 = 0x10;
+
+    pub const TSR1_SECONDS: u8 = 0x11;
+    pub const TSR2_SECONDS: u8 = 0x17;
+    pub const TSR3_SECONDS: u8 = 0x1D;
+    pub const TSR_MODE: u8 = 0x23;
+
     pub const OFFSET: u8 = 0x24;
     pub const OSCILLATOR: u8 = 0x25;
     pub const BATTERY_SWITCH: u8 = 0x26;
@@ -28,7 +49,7 @@ impl Register {
     pub const RESETS: u8 = 0x2F;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LoadCapacitance {
     Cl7pF,
     Cl6pF,
@@ -59,7 +80,7 @@ impl LoadCapacitance {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CrystalDrive {
     /// Normal drive, R_S(max) = 100 kOhm
     Normal,
@@ -69,6 +90,17 @@ pub enum CrystalDrive {
     High,
 }
 
+impl From<u8> for CrystalDrive {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::Normal,
+            0b01 => Self::Low,
+            0b10 | 0b11 => Self::High,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl CrystalDrive {
     pub fn as_u8(self) -> u8 {
         match self {
@@ -79,8 +111,8 @@ impl CrystalDrive {
     }
 }
 
-#[derive(Debug)]
-pub struct OscillatorReg(u8);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OscillatorReg(pub(crate) u8);
 
 impl OscillatorReg {
     pub const CLKIV: u8 = 7;
@@ -96,8 +128,41 @@ impl OscillatorReg {
         self.0 & (1 << Self::CLK_12_24) > 0
     }
 
+    pub fn with_12h_clock(self, enabled: bool) -> Self {
+        Self(if enabled {
+            self.0 | (1 << Self::CLK_12_24)
+        } else {
+            self.0 & !(1 << Self::CLK_12_24)
+        })
+    }
+
+    #[deprecated(note = "use `load_capacitance` instead")]
     pub fn load_capcitance(&self) -> LoadCapacitance {
-        LoadCapacitance::from(self.0 & Self::CL_MASK)
+        self.load_capacitance()
+    }
+
+    pub fn load_capacitance(&self) -> LoadCapacitance {
+        LoadCapacitance::from((self.0 >> Self::CL) & Self::CL_MASK)
+    }
+
+    pub fn offset_mode(&self) -> OffsetMode {
+        if self.0 & (1 << Self::OFFM) > 0 {
+            OffsetMode::Fast
+        } else {
+            OffsetMode::Normal
+        }
+    }
+
+    pub fn is_low_jitter(&self) -> bool {
+        self.0 & (1 << Self::LOWJ) > 0
+    }
+
+    pub fn crystal_drive(&self) -> CrystalDrive {
+        CrystalDrive::from((self.0 >> Self::OSCD) & Self::OSCD_MASK)
+    }
+
+    pub fn is_clockout_inverted(&self) -> bool {
+        self.0 & (1 << Self::CLKIV) > 0
     }
 
     pub fn with_load_capacitance(self, lc: LoadCapacitance) -> Self {
@@ -136,13 +201,7 @@ impl OscillatorReg {
     }
 }
 
-impl Default for OscillatorReg {
-    fn default() -> Self {
-        Self(0x00)
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClockOutputFrequency {
     F32768,
     F16384,
@@ -156,7 +215,7 @@ pub enum ClockOutputFrequency {
 
 impl From<u8> for ClockOutputFrequency {
     fn from(val: u8) -> Self {
-        match val & 0b11 {
+        match val & 0b111 {
             0b000 => Self::F32768,
             0b001 => Self::F16384,
             0b010 => Self::F8192,
@@ -185,7 +244,112 @@ impl ClockOutputFrequency {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatterySwitchThreshold {
+    /// V_th = 1.5 V
+    V1_5,
+    /// V_th = 2.8 V
+    V2_8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatterySwitchMode {
+    /// Switch over when V_DD drops below V_th
+    Threshold,
+    /// Switch over when V_DD drops below V_BAT
+    Battery,
+    /// Switch over at the higher of V_th and V_BAT
+    Higher,
+    /// Switch over at the lower of V_th and V_BAT
+    Lower,
+}
+
+impl From<u8> for BatterySwitchMode {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::Threshold,
+            0b01 => Self::Battery,
+            0b10 => Self::Higher,
+            0b11 => Self::Lower,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl BatterySwitchMode {
+    pub fn as_u8(self) -> u8 {
+        match self {
+            BatterySwitchMode::Threshold => 0b00,
+            BatterySwitchMode::Battery => 0b01,
+            BatterySwitchMode::Higher => 0b10,
+            BatterySwitchMode::Lower => 0b11,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatterySwitchReg(pub(crate) u8);
+
+impl BatterySwitchReg {
+    pub const BSOFF: u8 = 4;
+    pub const BSRR: u8 = 3;
+    pub const BSM: u8 = 1;
+    pub const BSM_MASK: u8 = 0b11;
+    pub const BSTH: u8 = 0;
+
+    pub fn is_switch_over_enabled(&self) -> bool {
+        self.0 & (1 << Self::BSOFF) == 0
+    }
+
+    pub fn is_high_refresh_rate(&self) -> bool {
+        self.0 & (1 << Self::BSRR) > 0
+    }
+
+    pub fn mode(&self) -> BatterySwitchMode {
+        BatterySwitchMode::from((self.0 >> Self::BSM) & Self::BSM_MASK)
+    }
+
+    pub fn threshold(&self) -> BatterySwitchThreshold {
+        if self.0 & (1 << Self::BSTH) > 0 {
+            BatterySwitchThreshold::V2_8
+        } else {
+            BatterySwitchThreshold::V1_5
+        }
+    }
+
+    pub fn with_switch_over(self, enabled: bool) -> Self {
+        Self(if enabled {
+            self.0 & !(1 << Self::BSOFF)
+        } else {
+            self.0 | (1 << Self::BSOFF)
+        })
+    }
+
+    pub fn with_high_refresh_rate(self, high: bool) -> Self {
+        Self(if high {
+            self.0 | (1 << Self::BSRR)
+        } else {
+            self.0 & !(1 << Self::BSRR)
+        })
+    }
+
+    pub fn with_mode(self, mode: BatterySwitchMode) -> Self {
+        Self((self.0 & !(Self::BSM_MASK << Self::BSM)) | (mode.as_u8() << Self::BSM))
+    }
+
+    pub fn with_threshold(self, threshold: BatterySwitchThreshold) -> Self {
+        Self(match threshold {
+            BatterySwitchThreshold::V1_5 => self.0 & !(1 << Self::BSTH),
+            BatterySwitchThreshold::V2_8 => self.0 | (1 << Self::BSTH),
+        })
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PeriodicInterrupt {
     NoInterrupt,
     OncePerSecond,
@@ -193,6 +357,18 @@ pub enum PeriodicInterrupt {
     OncePerHour,
 }
 
+impl From<u8> for PeriodicInterrupt {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::NoInterrupt,
+            0b01 => Self::OncePerSecond,
+            0b10 => Self::OncePerMinute,
+            0b11 => Self::OncePerHour,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl PeriodicInterrupt {
     pub fn as_u8(&self) -> u8 {
         match self {
@@ -204,8 +380,8 @@ impl PeriodicInterrupt {
     }
 }
 
-#[derive(Debug)]
-pub struct FunctionReg(u8);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FunctionReg(pub(crate) u8);
 
 impl FunctionReg {
     pub const S_100TH: u8 = 7;
@@ -217,7 +393,7 @@ impl FunctionReg {
     pub const COF_MASK: u8 = 0b111;
 
     pub fn s100th_enabled(&self) -> bool {
-        self.0 & Self::S_100TH > 0
+        self.0 & (1 << Self::S_100TH) > 0
     }
 
     pub fn with_100th(self, enable: bool) -> Self {
@@ -228,6 +404,31 @@ impl FunctionReg {
         }
     }
 
+    pub fn is_stopwatch_mode(&self) -> bool {
+        self.0 & (1 << Self::RTCM) > 0
+    }
+
+    pub fn with_stopwatch_mode(self, enable: bool) -> Self {
+        if enable {
+            Self(self.0 | (1 << Self::RTCM))
+        } else {
+            Self(self.0 & !(1 << Self::RTCM))
+        }
+    }
+
+    /// Returns `true` if the TS pin controls the STOP bit as well
+    pub fn is_ts_pin_stop(&self) -> bool {
+        self.0 & (1 << Self::STOPM) > 0
+    }
+
+    pub fn with_ts_pin_stop(self, enable: bool) -> Self {
+        if enable {
+            Self(self.0 | (1 << Self::STOPM))
+        } else {
+            Self(self.0 & !(1 << Self::STOPM))
+        }
+    }
+
     pub fn clock_output_frequency(&self) -> ClockOutputFrequency {
         ClockOutputFrequency::from((self.0 >> Self::COF) & Self::COF_MASK)
     }
@@ -236,6 +437,10 @@ impl FunctionReg {
         Self((self.0 & !(Self::COF_MASK << Self::COF)) | (cof.as_u8() << Self::COF))
     }
 
+    pub fn periodic_interrupt(&self) -> PeriodicInterrupt {
+        PeriodicInterrupt::from((self.0 >> Self::PI) & Self::PI_MASK)
+    }
+
     pub fn with_periodic_interrupt(self, pi: PeriodicInterrupt) -> Self {
         Self((self.0 & !(Self::PI_MASK << Self::PI)) | (pi.as_u8() << Self::PI))
     }
@@ -245,13 +450,7 @@ impl FunctionReg {
     }
 }
 
-impl Default for FunctionReg {
-    fn default() -> Self {
-        Self(0x00)
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IntAPinMode {
     ClkOutputMode,
     BatteryModeIndication,
@@ -259,6 +458,18 @@ pub enum IntAPinMode {
     HiZ,
 }
 
+impl From<u8> for IntAPinMode {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::ClkOutputMode,
+            0b01 => Self::BatteryModeIndication,
+            0b10 => Self::IntA,
+            0b11 => Self::HiZ,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl IntAPinMode {
     pub fn as_u8(&self) -> u8 {
         match self {
@@ -270,30 +481,166 @@ impl IntAPinMode {
     }
 }
 
-#[derive(Debug)]
-pub struct PinIoReg(u8);
+/// Function of the TS pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsPinMode {
+    /// Disabled, high impedance
+    Disabled,
+    /// INTB interrupt output, push-pull
+    IntB,
+    /// Clock output, push-pull
+    ClkOutput,
+    /// Timestamp and stop input
+    Input,
+}
+
+impl From<u8> for TsPinMode {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::Disabled,
+            0b01 => Self::IntB,
+            0b10 => Self::ClkOutput,
+            0b11 => Self::Input,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl TsPinMode {
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            TsPinMode::Disabled => 0b00,
+            TsPinMode::IntB => 0b01,
+            TsPinMode::ClkOutput => 0b10,
+            TsPinMode::Input => 0b11,
+        }
+    }
+}
+
+/// Active level of the TS input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsLevel {
+    High,
+    Low,
+}
+
+/// Input type of the TS pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsInputType {
+    /// CMOS input, driven externally
+    Cmos,
+    /// Mechanical switch, sampled at 16 Hz with the internal pull-up
+    MechanicalSwitch,
+}
+
+/// Internal pull-up resistor of the TS pin in mechanical switch mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsPullUp {
+    R80k,
+    R40k,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PinIoReg(pub(crate) u8);
 
 impl PinIoReg {
+    pub const CLKPM: u8 = 7;
+    pub const TSPULL: u8 = 6;
+    pub const TSL: u8 = 5;
+    pub const TSIM: u8 = 4;
+    pub const TSPM: u8 = 2;
+    pub const TSPM_MASK: u8 = 0b11;
     pub const INTAPM: u8 = 0;
     pub const INTAPM_MASK: u8 = 0b11;
 
+    pub fn inta_pinmode(&self) -> IntAPinMode {
+        IntAPinMode::from((self.0 >> Self::INTAPM) & Self::INTAPM_MASK)
+    }
+
     pub fn with_inta_pinmode(self, intapm: IntAPinMode) -> Self {
         Self((self.0 & !(Self::INTAPM_MASK << Self::INTAPM)) | (intapm.as_u8() << Self::INTAPM))
     }
 
+    pub fn ts_pinmode(&self) -> TsPinMode {
+        TsPinMode::from((self.0 >> Self::TSPM) & Self::TSPM_MASK)
+    }
+
+    pub fn with_ts_pinmode(self, tspm: TsPinMode) -> Self {
+        Self((self.0 & !(Self::TSPM_MASK << Self::TSPM)) | (tspm.as_u8() << Self::TSPM))
+    }
+
+    /// Returns `true` if the CLK pin outputs the clock
+    pub fn is_clk_pin_enabled(&self) -> bool {
+        self.0 & (1 << Self::CLKPM) == 0
+    }
+
+    pub fn with_clk_pin(self, enabled: bool) -> Self {
+        Self(if enabled {
+            self.0 & !(1 << Self::CLKPM)
+        } else {
+            self.0 | (1 << Self::CLKPM)
+        })
+    }
+
+    pub fn ts_level(&self) -> TsLevel {
+        if self.0 & (1 << Self::TSL) > 0 {
+            TsLevel::Low
+        } else {
+            TsLevel::High
+        }
+    }
+
+    pub fn with_ts_level(self, level: TsLevel) -> Self {
+        Self(match level {
+            TsLevel::High => self.0 & !(1 << Self::TSL),
+            TsLevel::Low => self.0 | (1 << Self::TSL),
+        })
+    }
+
+    pub fn ts_input_type(&self) -> TsInputType {
+        if self.0 & (1 << Self::TSIM) > 0 {
+            TsInputType::MechanicalSwitch
+        } else {
+            TsInputType::Cmos
+        }
+    }
+
+    pub fn with_ts_input_type(self, input_type: TsInputType) -> Self {
+        Self(match input_type {
+            TsInputType::Cmos => self.0 & !(1 << Self::TSIM),
+            TsInputType::MechanicalSwitch => self.0 | (1 << Self::TSIM),
+        })
+    }
+
+    pub fn ts_pull_up(&self) -> TsPullUp {
+        if self.0 & (1 << Self::TSPULL) > 0 {
+            TsPullUp::R40k
+        } else {
+            TsPullUp::R80k
+        }
+    }
+
+    pub fn with_ts_pull_up(self, pull_up: TsPullUp) -> Self {
+        Self(match pull_up {
+            TsPullUp::R80k => self.0 & !(1 << Self::TSPULL),
+            TsPullUp::R40k => self.0 | (1 << Self::TSPULL),
+        })
+    }
+
     pub fn as_u8(&self) -> u8 {
         self.0
     }
 }
 
-impl Default for PinIoReg {
-    fn default() -> Self {
-        Self(0x00)
-    }
+/// Interrupt output pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptPin {
+    IntA,
+    IntB,
 }
 
-#[derive(Debug, Clone)]
-pub struct InterruptReg(u8);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterruptReg(pub(crate) u8);
 
 impl InterruptReg {
     pub const ILP: u8 = 7;
@@ -313,10 +660,54 @@ impl InterruptReg {
         })
     }
 
+    /// Returns `true` if the interrupt pin is held low until the flag is
+    /// cleared, `false` if it's pulsed
+    pub fn level(&self) -> bool {
+        self.0 & (1 << Self::ILP) > 0
+    }
+
+    pub fn periodic_interrupt(&self) -> bool {
+        self.0 & (1 << Self::PIE) > 0
+    }
+
+    pub fn offset_correction_interrupt(&self) -> bool {
+        self.0 & (1 << Self::OIE) > 0
+    }
+
+    pub fn alarm1_interrupt(&self) -> bool {
+        self.0 & (1 << Self::A1IE) > 0
+    }
+
+    pub fn alarm2_interrupt(&self) -> bool {
+        self.0 & (1 << Self::A2IE) > 0
+    }
+
+    pub fn battery_switch_interrupt(&self) -> bool {
+        self.0 & (1 << Self::BSIE) > 0
+    }
+
+    pub fn watchdog_interrupt(&self) -> bool {
+        self.0 & (1 << Self::WDIE) > 0
+    }
+
+    pub fn timestamp_interrupt(&self) -> bool {
+        self.0 & (1 << Self::TSRIE) > 0
+    }
+
     pub fn with_level(self, level: bool) -> Self {
         self.with_bit(Self::ILP, level)
     }
 
+    /// Sets `bit` if the interrupt is routed to `own_pin`, and clears it otherwise
+    pub(crate) fn with_route(
+        self,
+        bit: u8,
+        own_pin: InterruptPin,
+        pin: Option<InterruptPin>,
+    ) -> Self {
+        self.with_bit(bit, pin == Some(own_pin))
+    }
+
     pub fn with_periodic_interrupt(self, en: bool) -> Self {
         self.with_bit(Self::PIE, en)
     }
@@ -350,95 +741,576 @@ impl InterruptReg {
     }
 }
 
-impl Default for InterruptReg {
-    fn default() -> Self {
-        Self(0x00)
+/// Watchdog counter step size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogStepSize {
+    /// 4 Hz, 0.25 s per step
+    F4,
+    /// 1 Hz, 1 s per step
+    F1,
+    /// 1/4 Hz, 4 s per step
+    F1_4,
+    /// 1/16 Hz, 16 s per step
+    F1_16,
+}
+
+impl From<u8> for WatchdogStepSize {
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => Self::F4,
+            0b01 => Self::F1,
+            0b10 => Self::F1_4,
+            0b11 => Self::F1_16,
+            _ => unreachable!(),
+        }
     }
 }
 
-impl<I, E> Pcf85263a<I>
-where
-    I: RegisterAccess<Error = E>,
-{
-    pub(crate) fn write_register(&mut self, register: u8, value: u8) -> Result<(), Error<E>> {
-        self.interface
-            .write_register(register, value)
-            .map_err(Error::Interface)
+impl WatchdogStepSize {
+    pub fn as_u8(self) -> u8 {
+        match self {
+            WatchdogStepSize::F4 => 0b00,
+            WatchdogStepSize::F1 => 0b01,
+            WatchdogStepSize::F1_4 => 0b10,
+            WatchdogStepSize::F1_16 => 0b11,
+        }
     }
 
-    pub(crate) fn write_register_multiple(
-        &mut self,
-        start_register: u8,
-        values: &[u8],
-    ) -> Result<(), Error<E>> {
-        self.interface
-            .write_registers(start_register, values)
-            .map_err(Error::Interface)
+    pub const fn step_millis(&self) -> u32 {
+        match self {
+            WatchdogStepSize::F4 => 250,
+            WatchdogStepSize::F1 => 1_000,
+            WatchdogStepSize::F1_4 => 4_000,
+            WatchdogStepSize::F1_16 => 16_000,
+        }
     }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatchdogReg(pub(crate) u8);
+
+impl WatchdogReg {
+    pub const WDM: u8 = 7;
+    pub const WDR: u8 = 2;
+    pub const WDR_MASK: u8 = 0b11111;
+    pub const WDS: u8 = 0;
+    pub const WDS_MASK: u8 = 0b11;
 
-    pub(crate) fn read_register(&mut self, register: u8) -> Result<u8, Error<E>> {
-        self.interface
-            .read_register(register)
-            .map_err(Error::Interface)
+    pub fn repeat(&self) -> bool {
+        self.0 & (1 << Self::WDM) > 0
     }
 
-    pub(crate) fn read_register_multiple<const N: usize>(
-        &mut self,
-        start_register: u8,
-    ) -> Result<[u8; N], Error<E>> {
-        let mut values = [0u8; N];
+    /// Number of steps until the watchdog times out, 0 means the watchdog is disabled
+    pub fn period(&self) -> u8 {
+        (self.0 >> Self::WDR) & Self::WDR_MASK
+    }
+
+    pub fn step_size(&self) -> WatchdogStepSize {
+        WatchdogStepSize::from((self.0 >> Self::WDS) & Self::WDS_MASK)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.period() as u64 * self.step_size().step_millis() as u64)
+    }
 
-        self.interface
-            .read_registers(start_register, &mut values)
-            .map_err(Error::Interface)
-            .and(Ok(values))
+    pub fn with_repeat(self, repeat: bool) -> Self {
+        Self(if repeat {
+            self.0 | (1 << Self::WDM)
+        } else {
+            self.0 & !(1 << Self::WDM)
+        })
     }
 
-    pub fn read_oscillator_register(&mut self) -> Result<OscillatorReg, Error<E>> {
-        Ok(OscillatorReg(self.read_register(Register::OSCILLATOR)?))
+    pub fn with_period(self, period: u8) -> Self {
+        Self((self.0 & !(Self::WDR_MASK << Self::WDR)) | ((period & Self::WDR_MASK) << Self::WDR))
     }
 
-    pub fn read_function_register(&mut self) -> Result<FunctionReg, Error<E>> {
-        Ok(FunctionReg(self.read_register(Register::FUNCTION)?))
+    pub fn with_step_size(self, step: WatchdogStepSize) -> Self {
+        Self((self.0 & !(Self::WDS_MASK << Self::WDS)) | (step.as_u8() << Self::WDS))
     }
 
-    pub fn write_oscillator_register(&mut self, osc: OscillatorReg) -> Result<(), Error<E>> {
-        self.write_register(Register::OSCILLATOR, osc.as_u8())
+    pub fn as_u8(&self) -> u8 {
+        self.0
     }
+}
 
-    pub fn write_stop_register(&mut self, stop: bool) -> Result<(), Error<E>> {
-        self.write_register(Register::STOP_ENABLE, if stop { 1 } else { 0 })
+/// Commands accepted by the resets register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetCommand {
+    ClearPrescaler,
+    ClearTimestamps,
+    /// Resets all registers to their power-on defaults
+    SoftwareReset,
+}
+
+impl ResetCommand {
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            ResetCommand::ClearPrescaler => 0xA4,
+            ResetCommand::ClearTimestamps => 0x25,
+            ResetCommand::SoftwareReset => 0x2C,
+        }
     }
+}
 
-    pub fn clear_prescaler(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::RESETS, 0xA4)
+/// Flags register
+///
+/// The offset correction interrupt has no flag of its own, it only generates
+/// pulses on the interrupt pins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flags(pub(crate) u8);
+
+impl Flags {
+    pub const PIF: u8 = 7;
+    pub const A2F: u8 = 6;
+    pub const A1F: u8 = 5;
+    pub const WDF: u8 = 4;
+    pub const BSF: u8 = 3;
+    pub const TSR3F: u8 = 2;
+    pub const TSR2F: u8 = 1;
+    pub const TSR1F: u8 = 0;
+
+    pub const ALL: Self = Self(0xFF);
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
     }
 
-    pub fn write_offset_register(&mut self, offset: i8) -> Result<(), Error<E>> {
-        self.write_register(Register::OFFSET, offset.to_be_bytes()[0])
+    pub fn periodic(&self) -> bool {
+        self.0 & (1 << Self::PIF) > 0
     }
 
-    pub fn write_function_register(&mut self, fr: FunctionReg) -> Result<(), Error<E>> {
-        self.write_register(Register::FUNCTION, fr.as_u8())
+    pub fn alarm1(&self) -> bool {
+        self.0 & (1 << Self::A1F) > 0
     }
 
-    pub fn write_pinio_register(&mut self, pinio: PinIoReg) -> Result<(), Error<E>> {
-        self.write_register(Register::PIN_IO, pinio.as_u8())
+    pub fn alarm2(&self) -> bool {
+        self.0 & (1 << Self::A2F) > 0
     }
 
-    pub fn write_inta_register(&mut self, int: InterruptReg) -> Result<(), Error<E>> {
-        self.write_register(Register::INTA_ENABLE, int.as_u8())
+    pub fn watchdog(&self) -> bool {
+        self.0 & (1 << Self::WDF) > 0
     }
 
-    pub fn write_intb_register(&mut self, int: InterruptReg) -> Result<(), Error<E>> {
-        self.write_register(Register::INTB_ENABLE, int.as_u8())
+    pub fn battery_switch(&self) -> bool {
+        self.0 & (1 << Self::BSF) > 0
+    }
+
+    pub fn timestamp1(&self) -> bool {
+        self.0 & (1 << Self::TSR1F) > 0
+    }
+
+    pub fn timestamp2(&self) -> bool {
+        self.0 & (1 << Self::TSR2F) > 0
+    }
+
+    pub fn timestamp3(&self) -> bool {
+        self.0 & (1 << Self::TSR3F) > 0
+    }
+
+    pub fn with_periodic(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::PIF)
+        } else {
+            self.0 & !(1 << Self::PIF)
+        })
+    }
+
+    pub fn with_alarm1(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::A1F)
+        } else {
+            self.0 & !(1 << Self::A1F)
+        })
+    }
+
+    pub fn with_alarm2(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::A2F)
+        } else {
+            self.0 & !(1 << Self::A2F)
+        })
+    }
+
+    pub fn with_watchdog(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::WDF)
+        } else {
+            self.0 & !(1 << Self::WDF)
+        })
+    }
+
+    pub fn with_battery_switch(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::BSF)
+        } else {
+            self.0 & !(1 << Self::BSF)
+        })
+    }
+
+    pub fn with_timestamp1(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::TSR1F)
+        } else {
+            self.0 & !(1 << Self::TSR1F)
+        })
+    }
+
+    pub fn with_timestamp2(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::TSR2F)
+        } else {
+            self.0 & !(1 << Self::TSR2F)
+        })
+    }
+
+    pub fn with_timestamp3(self, set: bool) -> Self {
+        Self(if set {
+            self.0 | (1 << Self::TSR3F)
+        } else {
+            self.0 & !(1 << Self::TSR3F)
+        })
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Implements the register access methods of `$driver`.
+///
+/// Both drivers are generated from the same source: the blocking one with
+/// empty `$async` and `$await`, the async one in `asynch` with `async` and
+/// `.await`. The other modules follow the same pattern.
+macro_rules! impl_register_access {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            pub(crate) $($async)? fn write_register(
+                &mut self,
+                register: u8,
+                value: u8,
+            ) -> Result<(), Error<E>> {
+                self.interface
+                    .write_register(register, value)
+                    $($await)*
+                    .map_err(Error::Interface)
+            }
+
+            pub(crate) $($async)? fn write_register_multiple(
+                &mut self,
+                start_register: u8,
+                values: &[u8],
+            ) -> Result<(), Error<E>> {
+                self.interface
+                    .write_registers(start_register, values)
+                    $($await)*
+                    .map_err(Error::Interface)
+            }
+
+            pub(crate) $($async)? fn read_register(
+                &mut self,
+                register: u8,
+            ) -> Result<u8, Error<E>> {
+                self.interface
+                    .read_register(register)
+                    $($await)*
+                    .map_err(Error::Interface)
+            }
+
+            pub(crate) $($async)? fn read_register_multiple<const N: usize>(
+                &mut self,
+                start_register: u8,
+            ) -> Result<[u8; N], Error<E>> {
+                let mut values = [0u8; N];
+
+                self.interface
+                    .read_registers(start_register, &mut values)
+                    $($await)*
+                    .map_err(Error::Interface)
+                    .and(Ok(values))
+            }
+
+            pub $($async)? fn read_oscillator_register(
+                &mut self,
+            ) -> Result<OscillatorReg, Error<E>> {
+                let osc = OscillatorReg(self.read_register(Register::OSCILLATOR)$($await)*?);
+                self.clock_12h = Some(osc.is_12h_clock());
+
+                Ok(osc)
+            }
+
+            /// Returns the 12/24h mode of the clock, only reading the oscillator
+            /// register if it isn't known yet.
+            pub(crate) $($async)? fn is_12h_clock(&mut self) -> Result<bool, Error<E>> {
+                match self.clock_12h {
+                    Some(clock_12h) => Ok(clock_12h),
+                    None => Ok(self.read_oscillator_register()$($await)*?.is_12h_clock()),
+                }
+            }
+
+            pub $($async)? fn read_function_register(&mut self) -> Result<FunctionReg, Error<E>> {
+                let fr = FunctionReg(self.read_register(Register::FUNCTION)$($await)*?);
+                self.s100th = Some(fr.s100th_enabled());
+
+                Ok(fr)
+            }
+
+            /// Returns whether the 100th seconds counter is enabled, only reading the
+            /// function register if it isn't known yet.
+            pub(crate) $($async)? fn is_100th_mode(&mut self) -> Result<bool, Error<E>> {
+                match self.s100th {
+                    Some(s100th) => Ok(s100th),
+                    None => Ok(self.read_function_register()$($await)*?.s100th_enabled()),
+                }
+            }
+
+            /// Forgets the cached 12/24h and 100th seconds modes, so the next access
+            /// reads them from the chip again.
+            ///
+            /// The driver keeps them up to date as long as the oscillator and
+            /// function registers are only changed through it. Call this if they
+            /// may have been changed behind its back, e.g. by another bus master or
+            /// a reset the driver didn't issue.
+            pub fn invalidate_cache(&mut self) {
+                self.clock_12h = None;
+                self.s100th = None;
+            }
+
+            pub $($async)? fn read_inta_register(&mut self) -> Result<InterruptReg, Error<E>> {
+                Ok(InterruptReg(self.read_register(Register::INTA_ENABLE)$($await)*?))
+            }
+
+            pub $($async)? fn read_intb_register(&mut self) -> Result<InterruptReg, Error<E>> {
+                Ok(InterruptReg(self.read_register(Register::INTB_ENABLE)$($await)*?))
+            }
+
+            pub $($async)? fn write_oscillator_register(
+                &mut self,
+                osc: OscillatorReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::OSCILLATOR, osc.as_u8())$($await)*?;
+                self.clock_12h = Some(osc.is_12h_clock());
+
+                Ok(())
+            }
+
+            pub $($async)? fn read_stop_register(&mut self) -> Result<bool, Error<E>> {
+                Ok(self.read_register(Register::STOP_ENABLE)$($await)*? & 1 > 0)
+            }
+
+            pub $($async)? fn write_stop_register(&mut self, stop: bool) -> Result<(), Error<E>> {
+                self.write_register(Register::STOP_ENABLE, if stop { 1 } else { 0 })$($await)*
+            }
+
+            pub $($async)? fn reset(&mut self, cmd: ResetCommand) -> Result<(), Error<E>> {
+                self.write_register(Register::RESETS, cmd.as_u8())$($await)*?;
+
+                if cmd == ResetCommand::SoftwareReset {
+                    self.clock_12h = None;
+                    self.s100th = None;
+                }
+
+                Ok(())
+            }
+
+            pub $($async)? fn clear_prescaler(&mut self) -> Result<(), Error<E>> {
+                self.reset(ResetCommand::ClearPrescaler)$($await)*
+            }
+
+            /// Performs a software reset and verifies that the configuration
+            /// registers read back their power-on defaults.
+            ///
+            /// Returns [`Error::ResetFailed`] if any of them doesn't.
+            pub $($async)? fn factory_reset(&mut self) -> Result<(), Error<E>> {
+                self.reset(ResetCommand::SoftwareReset)$($await)*?;
+
+                let config = self.read_register_multiple(Register::OFFSET)$($await)*?;
+                check_power_on_config(config)?;
+
+                self.clock_12h = Some(false);
+                self.s100th = Some(false);
+
+                Ok(())
+            }
+
+            pub $($async)? fn read_offset_register(&mut self) -> Result<i8, Error<E>> {
+                Ok(self.read_register(Register::OFFSET)$($await)*? as i8)
+            }
+
+            pub $($async)? fn write_offset_register(&mut self, offset: i8) -> Result<(), Error<E>> {
+                self.write_register(Register::OFFSET, offset.to_be_bytes()[0])$($await)*
+            }
+
+            /// Sets the frequency offset correction in ppb, writing the offset mode
+            /// and the offset register together in a single burst.
+            ///
+            /// Returns [`Error::InvalidOffset`] if `offset_ppb` is out of range for `mode`.
+            pub $($async)? fn set_frequency_offset_ppb(
+                &mut self,
+                offset_ppb: i32,
+                mode: OffsetMode,
+            ) -> Result<(), Error<E>> {
+                let offset = frequency_offset_value(offset_ppb, mode)?;
+                let osc = self.read_oscillator_register()$($await)*?;
+
+                self.write_register_multiple(
+                    Register::OFFSET,
+                    &encode_frequency_offset(offset, mode, osc),
+                )
+                $($await)*
+            }
+
+            /// Returns the frequency offset correction currently applied, in ppb
+            pub $($async)? fn frequency_offset_ppb(&mut self) -> Result<i32, Error<E>> {
+                let values = self.read_register_multiple(Register::OFFSET)$($await)*?;
+                self.clock_12h = Some(OscillatorReg(values[1]).is_12h_clock());
+
+                Ok(decode_frequency_offset(values))
+            }
+
+            pub $($async)? fn write_function_register(
+                &mut self,
+                fr: FunctionReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::FUNCTION, fr.as_u8())$($await)*?;
+                self.s100th = Some(fr.s100th_enabled());
+
+                Ok(())
+            }
+
+            pub $($async)? fn read_pinio_register(&mut self) -> Result<PinIoReg, Error<E>> {
+                Ok(PinIoReg(self.read_register(Register::PIN_IO)$($await)*?))
+            }
+
+            pub $($async)? fn write_pinio_register(
+                &mut self,
+                pinio: PinIoReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::PIN_IO, pinio.as_u8())$($await)*
+            }
+
+            pub $($async)? fn write_inta_register(
+                &mut self,
+                int: InterruptReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::INTA_ENABLE, int.as_u8())$($await)*
+            }
+
+            pub $($async)? fn write_intb_register(
+                &mut self,
+                int: InterruptReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::INTB_ENABLE, int.as_u8())$($await)*
+            }
+
+            pub $($async)? fn read_battery_switch_register(
+                &mut self,
+            ) -> Result<BatterySwitchReg, Error<E>> {
+                Ok(BatterySwitchReg(
+                    self.read_register(Register::BATTERY_SWITCH)$($await)*?,
+                ))
+            }
+
+            pub $($async)? fn write_battery_switch_register(
+                &mut self,
+                bs: BatterySwitchReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::BATTERY_SWITCH, bs.as_u8())$($await)*
+            }
+
+            /// Routes the battery switch-over interrupt to `pin` like
+            /// [`Pcf85263a::route_interrupt`], or disables it if `None`.
+            pub $($async)? fn set_battery_switch_interrupt(
+                &mut self,
+                pin: Option<InterruptPin>,
+            ) -> Result<(), Error<E>> {
+                match pin {
+                    Some(pin) => self.route_interrupt(Event::BatterySwitch, pin)$($await)*,
+                    None => self.unroute_interrupt(Event::BatterySwitch)$($await)*,
+                }
+            }
+
+            pub $($async)? fn read_watchdog_register(&mut self) -> Result<WatchdogReg, Error<E>> {
+                Ok(WatchdogReg(self.read_register(Register::WATCHDOG)$($await)*?))
+            }
+
+            pub $($async)? fn write_watchdog_register(
+                &mut self,
+                wd: WatchdogReg,
+            ) -> Result<(), Error<E>> {
+                self.write_register(Register::WATCHDOG, wd.as_u8())$($await)*
+            }
+
+            pub $($async)? fn read_flags(&mut self) -> Result<Flags, Error<E>> {
+                Ok(Flags(self.read_register(Register::FLAGS)$($await)*?))
+            }
+
+            /// Clears all flags set in `mask`.
+            ///
+            /// The chip ANDs the written value with the current flags, so flags
+            /// that aren't part of `mask` are left untouched, even if they got
+            /// set after they were last read.
+            pub $($async)? fn clear_flags(&mut self, mask: Flags) -> Result<(), Error<E>> {
+                self.write_register(Register::FLAGS, !mask.as_u8())$($await)*
+            }
+
+            /// Routes the interrupt enabled by `bit` of the interrupt enable registers to
+            /// `pin` and disables it on the other pin. `None` disables it on both pins.
+            pub(crate) $($async)? fn set_interrupt_enable(
+                &mut self,
+                bit: u8,
+                pin: Option<InterruptPin>,
+            ) -> Result<(), Error<E>> {
+                let inta = self.read_inta_register()$($await)*?;
+                self.write_inta_register(inta.with_route(bit, InterruptPin::IntA, pin))$($await)*?;
+
+                let intb = self.read_intb_register()$($await)*?;
+                self.write_intb_register(intb.with_route(bit, InterruptPin::IntB, pin))$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_register_access;
+
+impl_register_access!(Pcf85263a, RegisterAccess, [], []);
+
+/// Checks the registers `OFFSET` to `INTB_ENABLE`, i.e. offset, oscillator,
+/// battery switch, pin IO, function, INTA and INTB enable, against their
+/// power-on defaults
+pub(crate) fn check_power_on_config<E>(values: [u8; 7]) -> Result<(), Error<E>> {
+    if values == [0; 7] {
+        Ok(())
+    } else {
+        Err(Error::ResetFailed)
     }
 }
 
+/// Encodes the registers `OFFSET` and `OSCILLATOR`, keeping the other bits of `osc`
+pub(crate) fn encode_frequency_offset(offset: i8, mode: OffsetMode, osc: OscillatorReg) -> [u8; 2] {
+    [offset as u8, osc.with_offset_mode(mode).as_u8()]
+}
+
+/// Decodes the registers `OFFSET` and `OSCILLATOR` into the correction in ppb
+pub(crate) fn decode_frequency_offset(values: [u8; 2]) -> i32 {
+    let [offset, osc] = values;
+
+    ppb_offset_for_offset_value(offset as i8, OscillatorReg(osc).offset_mode())
+}
+
 pub trait RegisterAccess {
     type Error;
 
     fn write_register(&mut self, register: u8, value: u8) -> Result<(), Self::Error>;
+    /// Writes `values` to consecutive registers starting at `start_register`.
+    ///
+    /// This has to be a single bus write, relying on the auto-increment of
+    /// the register address. The driver depends on it to update the time
+    /// and related registers together.
     fn write_registers(&mut self, start_register: u8, values: &[u8]) -> Result<(), Self::Error>;
 
     fn read_register(&mut self, register: u8) -> Result<u8, Self::Error>;
@@ -473,17 +1345,15 @@ where
     }
 
     fn write_registers(&mut self, start_register: u8, values: &[u8]) -> Result<(), Self::Error> {
-        // TODO make this more efficient using a single write
-
-        for (register, value) in values
-            .into_iter()
-            .enumerate()
-            .map(|(reg, &value)| (reg as u8 + start_register, value))
-        {
-            self.write_register(register, value)?;
-        }
-
-        Ok(())
+        // adjacent writes in a transaction are sent without a repeated start, so the
+        // chip sees a single write and auto-increments the register address
+        self.i2c.transaction(
+            self.address,
+            &mut [
+                Operation::Write(&[start_register]),
+                Operation::Write(values),
+            ],
+        )
     }
 
     fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
@@ -524,6 +1394,212 @@ mod tests {
 
         reg = reg.with_crystal_drive(CrystalDrive::Low);
         assert_eq!(reg.as_u8(), 0xD5);
+
+        assert!(reg.is_clockout_inverted());
+        assert_eq!(reg.load_capacitance(), LoadCapacitance::Cl6pF);
+        assert_eq!(reg.offset_mode(), OffsetMode::Fast);
+        assert!(reg.is_low_jitter());
+        assert_eq!(reg.crystal_drive(), CrystalDrive::Low);
+        assert!(!reg.is_12h_clock());
+        assert!(reg.with_12h_clock(true).is_12h_clock());
+    }
+
+    #[test]
+    fn test_frequency_offset() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x21]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::OFFSET]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0xFE, 0x61]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OFFSET], vec![0xFE, 0x61]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.set_frequency_offset_ppb(-4_000, OffsetMode::Fast)
+            .unwrap();
+        assert_eq!(rtc.frequency_offset_ppb().unwrap(), -4_069);
+        assert!(matches!(
+            rtc.set_frequency_offset_ppb(500_000, OffsetMode::Fast),
+            Err(Error::InvalidOffset)
+        ));
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_function_reg() {
+        let frequencies = [
+            ClockOutputFrequency::F32768,
+            ClockOutputFrequency::F16384,
+            ClockOutputFrequency::F8192,
+            ClockOutputFrequency::F4096,
+            ClockOutputFrequency::F2048,
+            ClockOutputFrequency::F1024,
+            ClockOutputFrequency::F1,
+            ClockOutputFrequency::StaticLow,
+        ];
+
+        for cof in frequencies {
+            let reg = FunctionReg::default().with_clock_output_frequency(cof);
+            assert_eq!(reg.clock_output_frequency(), cof);
+        }
+
+        let reg = FunctionReg::default()
+            .with_100th(true)
+            .with_periodic_interrupt(PeriodicInterrupt::OncePerMinute);
+        assert_eq!(reg.as_u8(), 0xC0);
+        assert!(reg.s100th_enabled());
+        assert_eq!(reg.periodic_interrupt(), PeriodicInterrupt::OncePerMinute);
+        assert!(!reg.is_stopwatch_mode() && !reg.is_ts_pin_stop());
+    }
+
+    #[test]
+    fn test_pinio_reg() {
+        let reg = PinIoReg::default();
+        assert!(reg.is_clk_pin_enabled());
+        assert_eq!(reg.ts_pinmode(), TsPinMode::Disabled);
+
+        let reg = reg
+            .with_clk_pin(false)
+            .with_ts_pinmode(TsPinMode::IntB)
+            .with_inta_pinmode(IntAPinMode::IntA);
+        assert_eq!(reg.as_u8(), 0x86);
+        assert!(!reg.is_clk_pin_enabled());
+        assert_eq!(reg.ts_pinmode(), TsPinMode::IntB);
+        assert_eq!(reg.inta_pinmode(), IntAPinMode::IntA);
+
+        let reg = PinIoReg::default()
+            .with_ts_pinmode(TsPinMode::Input)
+            .with_ts_level(TsLevel::Low)
+            .with_ts_input_type(TsInputType::MechanicalSwitch)
+            .with_ts_pull_up(TsPullUp::R40k);
+        assert_eq!(reg.as_u8(), 0x7C);
+        assert_eq!(reg.ts_level(), TsLevel::Low);
+        assert_eq!(reg.ts_input_type(), TsInputType::MechanicalSwitch);
+        assert_eq!(reg.ts_pull_up(), TsPullUp::R40k);
+    }
+
+    #[test]
+    fn test_interrupt_reg() {
+        let reg = InterruptReg::default()
+            .with_level(true)
+            .with_alarm2_interrupt(true)
+            .with_watchdog_interrupt(true);
+        assert_eq!(reg.as_u8(), 0x89);
+
+        assert!(reg.level() && reg.alarm2_interrupt() && reg.watchdog_interrupt());
+        assert!(!reg.alarm1_interrupt() && !reg.periodic_interrupt());
+        assert_eq!(reg, InterruptReg(0x89));
+    }
+
+    #[test]
+    fn test_battery_switch_reg() {
+        let mut reg = BatterySwitchReg::default();
+        assert!(reg.is_switch_over_enabled());
+
+        reg = reg.with_switch_over(false);
+        assert_eq!(reg.as_u8(), 0x10);
+
+        reg = reg.with_high_refresh_rate(true);
+        assert_eq!(reg.as_u8(), 0x18);
+
+        reg = reg.with_mode(BatterySwitchMode::Lower);
+        assert_eq!(reg.as_u8(), 0x1E);
+
+        reg = reg.with_threshold(BatterySwitchThreshold::V2_8);
+        assert_eq!(reg.as_u8(), 0x1F);
+
+        reg = reg
+            .with_switch_over(true)
+            .with_mode(BatterySwitchMode::Battery);
+        assert_eq!(reg.as_u8(), 0x0B);
+
+        assert!(reg.is_switch_over_enabled());
+        assert!(reg.is_high_refresh_rate());
+        assert_eq!(reg.mode(), BatterySwitchMode::Battery);
+        assert_eq!(reg.threshold(), BatterySwitchThreshold::V2_8);
+    }
+
+    #[test]
+    fn test_battery_switch_interrupt() {
+        let expectations = [
+            // INTA is a clock output after power-on
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x03]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x02]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x02]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x02]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x00]),
+            // disabling leaves the pin mode alone
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x02]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x00]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        assert!(matches!(
+            rtc.set_battery_switch_interrupt(Some(InterruptPin::IntA)),
+            Err(Error::PinConflict)
+        ));
+        rtc.set_battery_switch_interrupt(Some(InterruptPin::IntA))
+            .unwrap();
+        rtc.set_battery_switch_interrupt(None).unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_clear_flags() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FLAGS], vec![0x61]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FLAGS, 0xDE]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let flags = rtc.read_flags().unwrap();
+        assert!(flags.alarm1() && flags.alarm2() && flags.timestamp1());
+        assert!(!flags.periodic() && !flags.watchdog());
+
+        rtc.clear_flags(Flags::default().with_alarm1(true).with_timestamp1(true))
+            .unwrap();
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_factory_reset() {
+        let expectations = [
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0x2C]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OFFSET], vec![0x00; 7]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0x2C]),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::OFFSET],
+                vec![0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00],
+            ),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.factory_reset().unwrap();
+        assert_eq!(rtc.clock_12h, Some(false));
+        assert_eq!(rtc.s100th, Some(false));
+
+        assert!(matches!(rtc.factory_reset(), Err(Error::ResetFailed)));
+        assert_eq!(rtc.clock_12h, None);
+        assert_eq!(rtc.s100th, None);
+
+        rtc.release().release().done();
     }
 
     #[test]
@@ -540,6 +1616,25 @@ mod tests {
         i2c.done();
     }
 
+    #[test]
+    fn test_write_register_multiple() {
+        let expectations = [
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x12]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x34, 0x56, 0x78]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+
+        let mut rtc = I2cInterface::new(i2c, DEFAULT_ADDRESS);
+        rtc.write_registers(0x12, &[0x34, 0x56, 0x78]).unwrap();
+
+        let mut i2c = rtc.release();
+
+        i2c.done();
+    }
+
     #[test]
     fn test_read_register() {
         let expectations = [I2cTransaction::write_read(
@@ -0,0 +1,458 @@
+use core::time::Duration;
+
+use crate::alarm::AlarmEnables;
+use crate::datetime::{
+    decode_bcd, decode_minutes, decode_seconds, decode_seconds_100th, encode_bcd,
+};
+use crate::register_access::{Flags, Register, RegisterAccess};
+use crate::{Error, Pcf85263a};
+
+/// Largest hour value of the elapsed time counter
+const MAX_HOURS: u32 = 999_999;
+
+/// Largest hour value alarm 2 can match in stop-watch mode
+const MAX_ALARM2_HOURS: u32 = 9_999;
+
+/// The chip running in stop-watch mode.
+///
+/// Instead of the calendar, the time registers hold an elapsed time counter
+/// with a resolution of 1/100 s and up to 999,999 hours. Create it with
+/// [`Pcf85263a::into_stopwatch`].
+pub struct Stopwatch<I> {
+    rtc: Pcf85263a<I>,
+}
+
+/// Implements the stop-watch mode of `$driver` and `$stopwatch`, see
+/// `impl_register_access`
+macro_rules! impl_stopwatch {
+    (
+        $driver:ident,
+        $stopwatch:ident,
+        $access:ident,
+        [$($async:tt)?],
+        [$($await:tt)*]
+    ) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Switches the chip to stop-watch mode and enables the 100th seconds counter.
+            ///
+            /// The counter registers are left untouched, so a stop-watch that is
+            /// already running keeps counting across resets of the host. When switching
+            /// over from RTC mode, they still contain the calendar and should be
+            /// cleared with [`Stopwatch::reset`].
+            ///
+            /// On error, the driver is handed back along with it.
+            pub $($async)? fn into_stopwatch(mut self) -> Result<$stopwatch<I>, (Self, Error<E>)> {
+                let result = match self.read_function_register()$($await)* {
+                    Ok(function) => {
+                        self.write_function_register(
+                            function.with_stopwatch_mode(true).with_100th(true),
+                        )
+                        $($await)*
+                    }
+                    Err(e) => Err(e),
+                };
+
+                match result {
+                    Ok(()) => Ok($stopwatch { rtc: self }),
+                    Err(e) => Err((self, e)),
+                }
+            }
+        }
+
+        impl<I, E> $stopwatch<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Switches the chip back to RTC mode.
+            ///
+            /// The time registers keep the counter values and should be set afterwards.
+            /// On error, the stop-watch is handed back along with it.
+            pub $($async)? fn into_rtc(mut self) -> Result<$driver<I>, (Self, Error<E>)> {
+                let result = match self.rtc.read_function_register()$($await)* {
+                    Ok(function) => {
+                        self.rtc
+                            .write_function_register(function.with_stopwatch_mode(false))
+                            $($await)*
+                    }
+                    Err(e) => Err(e),
+                };
+
+                match result {
+                    Ok(()) => Ok(self.rtc),
+                    Err(e) => Err((self, e)),
+                }
+            }
+
+            /// Releases the interface without leaving stop-watch mode
+            pub fn release(self) -> I {
+                self.rtc.release()
+            }
+
+            pub $($async)? fn elapsed(&mut self) -> Result<Duration, Error<E>> {
+                let values = self
+                    .rtc
+                    .read_register_multiple(Register::SECONDS_100TH)
+                    $($await)*?;
+
+                Ok(decode_elapsed(values))
+            }
+
+            pub $($async)? fn start(&mut self) -> Result<(), Error<E>> {
+                self.rtc.write_stop_register(false)$($await)*
+            }
+
+            pub $($async)? fn stop(&mut self) -> Result<(), Error<E>> {
+                self.rtc.write_stop_register(true)$($await)*
+            }
+
+            pub $($async)? fn is_running(&mut self) -> Result<bool, Error<E>> {
+                Ok(!self.rtc.read_stop_register()$($await)*?)
+            }
+
+            /// Sets the counter to zero. A running stop-watch keeps running.
+            pub $($async)? fn reset(&mut self) -> Result<(), Error<E>> {
+                let running = self.is_running()$($await)*?;
+
+                self.stop()$($await)*?;
+                self.rtc.clear_prescaler()$($await)*?;
+                self.rtc
+                    .write_register_multiple(Register::SECONDS_100TH, &[0; 6])
+                    $($await)*?;
+
+                if running {
+                    self.start()$($await)*?;
+                }
+
+                Ok(())
+            }
+
+            /// Sets alarm 1 to fire once the counter reaches `elapsed`, or disables it
+            /// if `None`. The alarm has a resolution of one second, fractions are ignored.
+            ///
+            /// Returns [`Error::InvalidAlarm`] without writing anything if `elapsed`
+            /// exceeds 999,999 hours.
+            pub $($async)? fn set_alarm1(
+                &mut self,
+                elapsed: Option<Duration>,
+            ) -> Result<(), Error<E>> {
+                let values = elapsed.map(encode_alarm1).transpose()?;
+
+                let other_enables = self.rtc.read_register(Register::ALARM_ENABLES)$($await)*?
+                    & !AlarmEnables::ALARM1_MASK;
+                self.rtc
+                    .write_register(Register::ALARM_ENABLES, other_enables)
+                    $($await)*?;
+
+                if let Some(values) = values {
+                    self.rtc
+                        .write_register_multiple(Register::SECOND_ALARM1, &values)
+                        $($await)*?;
+                    self.rtc
+                        .write_register(
+                            Register::ALARM_ENABLES,
+                            other_enables | AlarmEnables::ALARM1_MASK,
+                        )
+                        $($await)*?;
+                }
+
+                Ok(())
+            }
+
+            /// Sets alarm 2 to fire once the counter reaches `elapsed`, or disables it
+            /// if `None`. The alarm has a resolution of one minute and matches up to
+            /// 9,999 hours.
+            ///
+            /// Returns [`Error::InvalidAlarm`] without writing anything if `elapsed`
+            /// exceeds that.
+            pub $($async)? fn set_alarm2(
+                &mut self,
+                elapsed: Option<Duration>,
+            ) -> Result<(), Error<E>> {
+                let values = elapsed.map(encode_alarm2).transpose()?;
+
+                let other_enables = self.rtc.read_register(Register::ALARM_ENABLES)$($await)*?
+                    & !AlarmEnables::ALARM2_MASK;
+                self.rtc
+                    .write_register(Register::ALARM_ENABLES, other_enables)
+                    $($await)*?;
+
+                if let Some(values) = values {
+                    self.rtc
+                        .write_register_multiple(Register::MINUTE_ALARM2, &values)
+                        $($await)*?;
+                    self.rtc
+                        .write_register(
+                            Register::ALARM_ENABLES,
+                            other_enables | AlarmEnables::ALARM2_MASK,
+                        )
+                        $($await)*?;
+                }
+
+                Ok(())
+            }
+
+            pub $($async)? fn read_flags(&mut self) -> Result<Flags, Error<E>> {
+                self.rtc.read_flags()$($await)*
+            }
+
+            pub $($async)? fn clear_flags(&mut self, mask: Flags) -> Result<(), Error<E>> {
+                self.rtc.clear_flags(mask)$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_stopwatch;
+
+impl_stopwatch!(Pcf85263a, Stopwatch, RegisterAccess, [], []);
+
+/// Splits `elapsed` into hours, minutes and seconds
+fn split_duration<E>(elapsed: Duration, max_hours: u32) -> Result<(u32, u8, u8), Error<E>> {
+    let seconds = elapsed.as_secs();
+    let hours = seconds / 3600;
+
+    if hours > max_hours as u64 {
+        return Err(Error::InvalidAlarm);
+    }
+
+    Ok((
+        hours as u32,
+        (seconds / 60 % 60) as u8,
+        (seconds % 60) as u8,
+    ))
+}
+
+/// Encodes the registers `SECOND_ALARM1` to `MONTH_ALARM1`, which hold the
+/// hour counter in stop-watch mode
+pub(crate) fn encode_alarm1<E>(elapsed: Duration) -> Result<[u8; 5], Error<E>> {
+    let (hours, minutes, seconds) = split_duration(elapsed, MAX_HOURS)?;
+    let [h0, h1, h2] = encode_hours_counter(hours);
+
+    Ok([encode_bcd(seconds), encode_bcd(minutes), h0, h1, h2])
+}
+
+/// Encodes the registers `MINUTE_ALARM2` to `WEEKDAY_ALARM2`
+pub(crate) fn encode_alarm2<E>(elapsed: Duration) -> Result<[u8; 3], Error<E>> {
+    let (hours, minutes, _) = split_duration(elapsed, MAX_ALARM2_HOURS)?;
+    let [h0, h1, _] = encode_hours_counter(hours);
+
+    Ok([encode_bcd(minutes), h0, h1])
+}
+
+/// Encodes the hours into the three hour counter registers, least
+/// significant digits first
+fn encode_hours_counter(hours: u32) -> [u8; 3] {
+    [
+        encode_bcd((hours % 100) as u8),
+        encode_bcd((hours / 100 % 100) as u8),
+        encode_bcd((hours / 10_000 % 100) as u8),
+    ]
+}
+
+pub(crate) fn decode_elapsed(values: [u8; 6]) -> Duration {
+    let [seconds_100th, seconds, minutes, h0, h1, h2] = values;
+
+    let hours =
+        decode_bcd(h0) as u64 + decode_bcd(h1) as u64 * 100 + decode_bcd(h2) as u64 * 10_000;
+    let seconds =
+        hours * 3600 + decode_minutes(minutes) as u64 * 60 + decode_seconds(seconds) as u64;
+
+    Duration::from_secs(seconds)
+        + Duration::from_millis(decode_seconds_100th(seconds_100th) as u64 * 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_mode_switch() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00])
+                .with_error(ErrorKind::Other),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x90]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x90]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x80])
+                .with_error(ErrorKind::Other),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x90]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x80]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        // the driver is handed back on errors
+        let (rtc, error) = rtc.into_stopwatch().err().unwrap();
+        assert!(matches!(error, Error::Interface(ErrorKind::Other)));
+
+        let stopwatch = rtc.into_stopwatch().ok().unwrap();
+        let (stopwatch, error) = stopwatch.into_rtc().err().unwrap();
+        assert!(matches!(error, Error::Interface(ErrorKind::Other)));
+
+        let rtc = stopwatch.into_rtc().ok().unwrap();
+
+        rtc.release().release().done();
+    }
+
+    /// Switches to stop-watch mode, then expects `expectations`
+    fn stopwatch(expectations: &[I2cTransaction]) -> Stopwatch<I2cInterface<I2cMock>> {
+        let mut all = vec![
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::FUNCTION], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::FUNCTION, 0x90]),
+        ];
+        all.extend_from_slice(expectations);
+
+        let i2c = I2cMock::new(&all);
+        let rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.into_stopwatch().ok().unwrap()
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let mut stopwatch = stopwatch(&[I2cTransaction::write_read(
+            DEFAULT_ADDRESS,
+            vec![Register::SECONDS_100TH],
+            vec![0x25, 0x07, 0x30, 0x34, 0x12, 0x00],
+        )]);
+
+        assert_eq!(
+            stopwatch.elapsed().unwrap(),
+            Duration::from_secs(1234 * 3600 + 30 * 60 + 7) + Duration::from_millis(250)
+        );
+
+        stopwatch.release().release().done();
+    }
+
+    #[test]
+    fn test_reset() {
+        let clear_counter = [
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::RESETS, 0xA4]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::SECONDS_100TH]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x00; 6]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+        ];
+
+        let mut expectations = vec![
+            // running, so it's restarted
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE], vec![0x00]),
+        ];
+        expectations.extend_from_slice(&clear_counter);
+        expectations.extend([
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE, 0x00]),
+            // stopped, so it stays stopped
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::STOP_ENABLE], vec![0x01]),
+        ]);
+        expectations.extend_from_slice(&clear_counter);
+
+        let mut stopwatch = stopwatch(&expectations);
+
+        stopwatch.reset().unwrap();
+        stopwatch.reset().unwrap();
+
+        stopwatch.release().release().done();
+    }
+
+    #[test]
+    fn test_set_alarms() {
+        let mut stopwatch = stopwatch(&[
+            // alarm 2 enables are kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0xE0]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xE0]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::SECOND_ALARM1]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x03, 0x02, 0x56, 0x34, 0x12]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xFF]),
+            // alarm 1 enables are kept
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0xFF]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0x1F]),
+            I2cTransaction::transaction_start(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::MINUTE_ALARM2]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![0x45, 0x23, 0x01]),
+            I2cTransaction::transaction_end(DEFAULT_ADDRESS),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xFF]),
+            // disabling only clears the enables
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0xFF]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0xE0]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES], vec![0xE0]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::ALARM_ENABLES, 0x00]),
+        ]);
+
+        // fractions are ignored
+        stopwatch
+            .set_alarm1(Some(
+                Duration::from_secs(123_456 * 3600 + 2 * 60 + 3) + Duration::from_millis(500),
+            ))
+            .unwrap();
+        stopwatch
+            .set_alarm2(Some(Duration::from_secs(123 * 3600 + 45 * 60)))
+            .unwrap();
+        stopwatch.set_alarm1(None).unwrap();
+        stopwatch.set_alarm2(None).unwrap();
+
+        stopwatch.release().release().done();
+    }
+
+    #[test]
+    fn test_set_alarm_out_of_range() {
+        let mut stopwatch = stopwatch(&[]);
+
+        // the alarms are left enabled
+        assert!(matches!(
+            stopwatch.set_alarm1(Some(Duration::from_secs((MAX_HOURS as u64 + 1) * 3600))),
+            Err(Error::InvalidAlarm)
+        ));
+        assert!(matches!(
+            stopwatch.set_alarm2(Some(Duration::from_secs(
+                (MAX_ALARM2_HOURS as u64 + 1) * 3600
+            ))),
+            Err(Error::InvalidAlarm)
+        ));
+
+        stopwatch.release().release().done();
+    }
+
+    #[test]
+    fn test_decode_elapsed() {
+        assert_eq!(decode_elapsed([0; 6]), Duration::ZERO);
+        assert_eq!(
+            decode_elapsed([0x99, 0x59, 0x59, 0x99, 0x99, 0x99]),
+            Duration::from_secs(999_999 * 3600 + 59 * 60 + 59) + Duration::from_millis(990)
+        );
+        assert_eq!(
+            decode_elapsed([0x25, 0x80 | 0x07, 0x30, 0x34, 0x12, 0x00]),
+            Duration::from_secs(1234 * 3600 + 30 * 60 + 7) + Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn test_encode_hours_counter() {
+        assert_eq!(encode_hours_counter(0), [0x00, 0x00, 0x00]);
+        assert_eq!(encode_hours_counter(123_456), [0x56, 0x34, 0x12]);
+        assert_eq!(encode_hours_counter(MAX_HOURS), [0x99, 0x99, 0x99]);
+    }
+
+    #[test]
+    fn test_split_duration() {
+        assert_eq!(
+            split_duration::<()>(Duration::from_secs(3 * 3600 + 25 * 60 + 9), MAX_HOURS).unwrap(),
+            (3, 25, 9)
+        );
+        assert!(split_duration::<()>(
+            Duration::from_secs((MAX_ALARM2_HOURS as u64 + 1) * 3600),
+            MAX_ALARM2_HOURS
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,385 @@
+use crate::datetime::decode_datetime;
+use crate::interrupt::interrupt_pinio;
+use crate::register_access::{
+    Flags, InterruptPin, InterruptReg, PinIoReg, Register, RegisterAccess, ResetCommand,
+    TsInputType, TsLevel, TsPinMode, TsPullUp,
+};
+use crate::{Error, NaiveDateTime, Pcf85263a};
+
+/// Timestamp register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsrSlot {
+    Tsr1,
+    Tsr2,
+    Tsr3,
+}
+
+impl TsrSlot {
+    pub(crate) fn first_register(&self) -> u8 {
+        match self {
+            TsrSlot::Tsr1 => Register::TSR1_SECONDS,
+            TsrSlot::Tsr2 => Register::TSR2_SECONDS,
+            TsrSlot::Tsr3 => Register::TSR3_SECONDS,
+        }
+    }
+
+    /// Position and mask of the mode bits in `TSR_MODE`
+    fn mode_bits(&self) -> (u8, u8) {
+        match self {
+            TsrSlot::Tsr1 => (0, 0b11),
+            TsrSlot::Tsr2 => (2, 0b111),
+            TsrSlot::Tsr3 => (6, 0b11),
+        }
+    }
+
+    /// Replaces the mode bits of the register in `tsr_mode` with `value`
+    pub(crate) fn with_mode_value(&self, tsr_mode: u8, value: u8) -> u8 {
+        let (shift, mask) = self.mode_bits();
+
+        (tsr_mode & !(mask << shift)) | (value << shift)
+    }
+
+    /// Returns the mode of the register in `tsr_mode`
+    pub(crate) fn mode(&self, tsr_mode: u8) -> TimestampMode {
+        let (shift, mask) = self.mode_bits();
+
+        TimestampMode::from_u8(*self, (tsr_mode >> shift) & mask)
+    }
+}
+
+/// Event captured by a timestamp register.
+///
+/// Not every register supports every mode:
+///
+/// | Mode                 | TSR1 | TSR2 | TSR3 |
+/// |----------------------|------|------|------|
+/// | `FirstTsEvent`       | x    | x    |      |
+/// | `LastTsEvent`        | x    | x    |      |
+/// | `FirstBatterySwitch` |      | x    | x    |
+/// | `LastBatterySwitch`  |      | x    | x    |
+/// | `LastVddSwitch`      |      | x    | x    |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampMode {
+    Disabled,
+    /// First event on the TS pin
+    FirstTsEvent,
+    /// Last event on the TS pin
+    LastTsEvent,
+    /// First switch-over to battery
+    FirstBatterySwitch,
+    /// Last switch-over to battery
+    LastBatterySwitch,
+    /// Last switch-over back to V_DD
+    LastVddSwitch,
+}
+
+impl TimestampMode {
+    pub(crate) fn as_u8(&self, slot: TsrSlot) -> Option<u8> {
+        match (slot, self) {
+            (_, TimestampMode::Disabled) => Some(0b000),
+            (TsrSlot::Tsr1, TimestampMode::FirstTsEvent) => Some(0b01),
+            (TsrSlot::Tsr1, TimestampMode::LastTsEvent) => Some(0b10),
+            (TsrSlot::Tsr1, _) => None,
+            (TsrSlot::Tsr2, TimestampMode::FirstBatterySwitch) => Some(0b001),
+            (TsrSlot::Tsr2, TimestampMode::LastBatterySwitch) => Some(0b010),
+            (TsrSlot::Tsr2, TimestampMode::LastVddSwitch) => Some(0b011),
+            (TsrSlot::Tsr2, TimestampMode::FirstTsEvent) => Some(0b100),
+            (TsrSlot::Tsr2, TimestampMode::LastTsEvent) => Some(0b101),
+            (TsrSlot::Tsr3, TimestampMode::FirstBatterySwitch) => Some(0b01),
+            (TsrSlot::Tsr3, TimestampMode::LastBatterySwitch) => Some(0b10),
+            (TsrSlot::Tsr3, TimestampMode::LastVddSwitch) => Some(0b11),
+            (TsrSlot::Tsr3, _) => None,
+        }
+    }
+
+    pub(crate) fn from_u8(slot: TsrSlot, val: u8) -> Self {
+        match (slot, val) {
+            (TsrSlot::Tsr1, 0b01) => TimestampMode::FirstTsEvent,
+            (TsrSlot::Tsr1, 0b10) => TimestampMode::LastTsEvent,
+            (TsrSlot::Tsr2, 0b001) => TimestampMode::FirstBatterySwitch,
+            (TsrSlot::Tsr2, 0b010) => TimestampMode::LastBatterySwitch,
+            (TsrSlot::Tsr2, 0b011) => TimestampMode::LastVddSwitch,
+            (TsrSlot::Tsr2, 0b100) => TimestampMode::FirstTsEvent,
+            (TsrSlot::Tsr2, 0b101) => TimestampMode::LastTsEvent,
+            (TsrSlot::Tsr3, 0b01) => TimestampMode::FirstBatterySwitch,
+            (TsrSlot::Tsr3, 0b10) => TimestampMode::LastBatterySwitch,
+            (TsrSlot::Tsr3, 0b11) => TimestampMode::LastVddSwitch,
+            _ => TimestampMode::Disabled,
+        }
+    }
+}
+
+/// Configuration of the TS pin as timestamp input, e.g. for a tamper switch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TsInputConfig {
+    pub level: TsLevel,
+    /// A mechanical switch is sampled at 16 Hz, which debounces it
+    pub input_type: TsInputType,
+    /// Only used for [`TsInputType::MechanicalSwitch`]
+    pub pull_up: TsPullUp,
+    /// Pin signalling timestamp events, `None` disables the interrupt
+    pub interrupt: Option<InterruptPin>,
+}
+
+impl TsInputConfig {
+    /// Configures the TS input in `pinio` and switches the interrupt pin to
+    /// interrupt output. Returns [`Error::PinConflict`] if it can't be.
+    pub(crate) fn pinio_register<E>(&self, pinio: PinIoReg) -> Result<PinIoReg, Error<E>> {
+        let pinio = pinio
+            .with_ts_pinmode(TsPinMode::Input)
+            .with_ts_level(self.level)
+            .with_ts_input_type(self.input_type)
+            .with_ts_pull_up(self.pull_up);
+
+        match self.interrupt {
+            Some(pin) => interrupt_pinio(pinio, pin).ok_or(Error::PinConflict),
+            None => Ok(pinio),
+        }
+    }
+}
+
+/// Events captured on the TS pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TamperEvent {
+    pub first: NaiveDateTime,
+    pub last: NaiveDateTime,
+}
+
+/// Implements the timestamp methods of `$driver`, see `impl_register_access`
+macro_rules! impl_timestamp {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Configures the TS pin as input, with TSR1 capturing the first and
+            /// TSR2 the last event, and routes the timestamp interrupt like
+            /// [`Pcf85263a::route_interrupt`].
+            ///
+            /// Returns [`Error::PinConflict`] without writing anything if the
+            /// interrupt can't be routed. INTB shares the TS pin, so it always
+            /// conflicts, and INTA does while it's configured as clock output or
+            /// battery mode indication.
+            pub $($async)? fn configure_ts_input(
+                &mut self,
+                config: &TsInputConfig,
+            ) -> Result<(), Error<E>> {
+                let pinio = config.pinio_register(self.read_pinio_register()$($await)*?)?;
+                self.write_pinio_register(pinio)$($await)*?;
+
+                self.set_timestamp_mode(TsrSlot::Tsr1, TimestampMode::FirstTsEvent)$($await)*?;
+                self.set_timestamp_mode(TsrSlot::Tsr2, TimestampMode::LastTsEvent)$($await)*?;
+
+                self.set_interrupt_enable(InterruptReg::TSRIE, config.interrupt)$($await)*
+            }
+
+            /// Returns the first and last event captured since the timestamps were
+            /// last cleared with [`Pcf85263a::clear_timestamps`], or `None` if there
+            /// was none.
+            pub $($async)? fn tamper_event(&mut self) -> Result<Option<TamperEvent>, Error<E>> {
+                let Some(first) = self.read_timestamp(TsrSlot::Tsr1)$($await)*? else {
+                    return Ok(None);
+                };
+                let last = self.read_timestamp(TsrSlot::Tsr2)$($await)*?.unwrap_or(first);
+
+                Ok(Some(TamperEvent { first, last }))
+            }
+
+            /// Selects the event captured by the timestamp register `slot`.
+            ///
+            /// Returns [`Error::InvalidTimestampMode`] if the register doesn't support `mode`.
+            pub $($async)? fn set_timestamp_mode(
+                &mut self,
+                slot: TsrSlot,
+                mode: TimestampMode,
+            ) -> Result<(), Error<E>> {
+                let value = mode.as_u8(slot).ok_or(Error::InvalidTimestampMode)?;
+
+                let tsr_mode = self.read_register(Register::TSR_MODE)$($await)*?;
+                self.write_register(Register::TSR_MODE, slot.with_mode_value(tsr_mode, value))
+                    $($await)*
+            }
+
+            pub $($async)? fn timestamp_mode(
+                &mut self,
+                slot: TsrSlot,
+            ) -> Result<TimestampMode, Error<E>> {
+                let tsr_mode = self.read_register(Register::TSR_MODE)$($await)*?;
+
+                Ok(slot.mode(tsr_mode))
+            }
+
+            /// Reads the timestamp register `slot`. Returns `None` if no event was captured.
+            pub $($async)? fn read_timestamp(
+                &mut self,
+                slot: TsrSlot,
+            ) -> Result<Option<NaiveDateTime>, Error<E>> {
+                let clock_12h = self.is_12h_clock()$($await)*?;
+                let values = self.read_register_multiple(slot.first_register())$($await)*?;
+
+                Ok(decode_datetime(values, clock_12h))
+            }
+
+            /// Clears all timestamp registers and their flags, re-arming
+            /// registers in first event mode.
+            pub $($async)? fn clear_timestamps(&mut self) -> Result<(), Error<E>> {
+                self.reset(ResetCommand::ClearTimestamps)$($await)*?;
+                self.clear_flags(
+                    Flags::default()
+                        .with_timestamp1(true)
+                        .with_timestamp2(true)
+                        .with_timestamp3(true),
+                )$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_timestamp;
+
+impl_timestamp!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{I2cInterface, NaiveDate, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    #[test]
+    fn test_timestamp_modes() {
+        let slots = [TsrSlot::Tsr1, TsrSlot::Tsr2, TsrSlot::Tsr3];
+        let modes = [
+            TimestampMode::Disabled,
+            TimestampMode::FirstTsEvent,
+            TimestampMode::LastTsEvent,
+            TimestampMode::FirstBatterySwitch,
+            TimestampMode::LastBatterySwitch,
+            TimestampMode::LastVddSwitch,
+        ];
+
+        for slot in slots {
+            for mode in modes {
+                if let Some(val) = mode.as_u8(slot) {
+                    assert_eq!(TimestampMode::from_u8(slot, val), mode);
+                }
+            }
+        }
+
+        assert_eq!(TimestampMode::LastVddSwitch.as_u8(TsrSlot::Tsr1), None);
+        assert_eq!(TimestampMode::FirstTsEvent.as_u8(TsrSlot::Tsr3), None);
+    }
+
+    #[test]
+    fn test_read_timestamp() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::TSR2_SECONDS],
+                vec![0x56, 0x34, 0x12, 0x28, 0x02, 0x24],
+            ),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::TSR3_SECONDS],
+                vec![0x00; 6],
+            ),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        assert_eq!(
+            rtc.read_timestamp(TsrSlot::Tsr2).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 28)
+                .unwrap()
+                .and_hms_opt(12, 34, 56)
+        );
+        assert_eq!(rtc.read_timestamp(TsrSlot::Tsr3).unwrap(), None);
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_configure_ts_input() {
+        let expectations = [
+            // INTA in hi-Z mode is switched to interrupt output
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x03]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::PIN_IO, 0x3E]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::TSR_MODE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::TSR_MODE, 0x01]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::TSR_MODE], vec![0x01]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::TSR_MODE, 0x15]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTA_ENABLE, 0x04]),
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE], vec![0x00]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::INTB_ENABLE, 0x00]),
+            // INTB shares the TS pin
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x3E]),
+            // INTA in its power-on clock output mode, nothing is written
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::PIN_IO], vec![0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let mut config = TsInputConfig {
+            level: TsLevel::Low,
+            input_type: TsInputType::MechanicalSwitch,
+            pull_up: TsPullUp::R80k,
+            interrupt: Some(InterruptPin::IntA),
+        };
+        rtc.configure_ts_input(&config).unwrap();
+
+        config.interrupt = Some(InterruptPin::IntB);
+        assert!(matches!(
+            rtc.configure_ts_input(&config),
+            Err(Error::PinConflict)
+        ));
+
+        config.interrupt = Some(InterruptPin::IntA);
+        assert!(matches!(
+            rtc.configure_ts_input(&config),
+            Err(Error::PinConflict)
+        ));
+
+        rtc.release().release().done();
+    }
+
+    #[test]
+    fn test_tamper_event() {
+        let expectations = [
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::OSCILLATOR], vec![0x00]),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::TSR1_SECONDS],
+                vec![0x00, 0x15, 0x03, 0x14, 0x10, 0x26],
+            ),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::TSR2_SECONDS],
+                vec![0x30, 0x45, 0x22, 0x15, 0x10, 0x26],
+            ),
+            I2cTransaction::write_read(
+                DEFAULT_ADDRESS,
+                vec![Register::TSR1_SECONDS],
+                vec![0x00; 6],
+            ),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        assert_eq!(
+            rtc.tamper_event().unwrap(),
+            Some(TamperEvent {
+                first: date.and_hms_opt(3, 15, 0).unwrap(),
+                last: date.succ_opt().unwrap().and_hms_opt(22, 45, 30).unwrap(),
+            })
+        );
+        assert_eq!(rtc.tamper_event().unwrap(), None);
+
+        rtc.release().release().done();
+    }
+}
@@ -0,0 +1,173 @@
+use core::time::Duration;
+
+use crate::register_access::{RegisterAccess, WatchdogReg, WatchdogStepSize};
+use crate::{Error, Pcf85263a};
+
+/// Watchdog configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Time until the watchdog fires if it isn't kicked.
+    ///
+    /// Has to be a multiple of one of the step sizes (0.25 s, 1 s, 4 s or 16 s)
+    /// of at most 31 steps, i.e. up to 7.75 s, 31 s, 124 s or 496 s.
+    pub timeout: Duration,
+    /// Restart the watchdog automatically after it fired
+    pub repeat: bool,
+}
+
+impl WatchdogConfig {
+    /// Returns the configuration of a watchdog register, `None` if the
+    /// watchdog is disabled.
+    pub fn from_register(wd: WatchdogReg) -> Option<Self> {
+        (wd.period() > 0).then(|| WatchdogConfig {
+            timeout: wd.timeout(),
+            repeat: wd.repeat(),
+        })
+    }
+
+    /// Returns the register value for this configuration, using the finest
+    /// step size that represents the timeout exactly.
+    pub fn register(&self) -> Option<WatchdogReg> {
+        let millis = self.timeout.as_millis();
+
+        if !self.timeout.subsec_nanos().is_multiple_of(1_000_000) {
+            return None;
+        }
+
+        [
+            WatchdogStepSize::F4,
+            WatchdogStepSize::F1,
+            WatchdogStepSize::F1_4,
+            WatchdogStepSize::F1_16,
+        ]
+        .into_iter()
+        .find_map(|step| {
+            let step_millis = step.step_millis() as u128;
+            let period = millis / step_millis;
+
+            (millis.is_multiple_of(step_millis)
+                && (1..=WatchdogReg::WDR_MASK as u128).contains(&period))
+            .then(|| {
+                WatchdogReg::default()
+                    .with_repeat(self.repeat)
+                    .with_period(period as u8)
+                    .with_step_size(step)
+            })
+        })
+    }
+}
+
+/// Implements the watchdog methods of `$driver`, see `impl_register_access`
+macro_rules! impl_watchdog {
+    ($driver:ident, $access:ident, [$($async:tt)?], [$($await:tt)*]) => {
+        impl<I, E> $driver<I>
+        where
+            I: $access<Error = E>,
+        {
+            /// Configures and starts the watchdog.
+            ///
+            /// Returns [`Error::InvalidWatchdogTimeout`] if the timeout can't be represented.
+            pub $($async)? fn configure_watchdog(
+                &mut self,
+                config: &WatchdogConfig,
+            ) -> Result<(), Error<E>> {
+                let wd = config.register().ok_or(Error::InvalidWatchdogTimeout)?;
+
+                self.write_watchdog_register(wd)$($await)*
+            }
+
+            /// Restarts the watchdog counter by writing back its current configuration
+            pub $($async)? fn kick_watchdog(&mut self) -> Result<(), Error<E>> {
+                let wd = self.read_watchdog_register()$($await)*?;
+
+                self.write_watchdog_register(wd)$($await)*
+            }
+
+            pub $($async)? fn disable_watchdog(&mut self) -> Result<(), Error<E>> {
+                self.write_watchdog_register(WatchdogReg::default())$($await)*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+pub(crate) use impl_watchdog;
+
+impl_watchdog!(Pcf85263a, RegisterAccess, [], []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_access::Register;
+    use crate::{I2cInterface, DEFAULT_ADDRESS};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+    fn register_value(millis: u64, repeat: bool) -> Option<u8> {
+        WatchdogConfig {
+            timeout: Duration::from_millis(millis),
+            repeat,
+        }
+        .register()
+        .map(|wd| wd.as_u8())
+    }
+
+    #[test]
+    fn test_watchdog_register() {
+        assert_eq!(register_value(250, false), Some(0x04));
+        assert_eq!(register_value(7_750, true), Some(0xFC));
+        assert_eq!(register_value(10_000, false), Some(0x29));
+        assert_eq!(register_value(60_000, false), Some(0x3E));
+        assert_eq!(register_value(496_000, false), Some(0x7F));
+
+        assert_eq!(register_value(0, false), None);
+        assert_eq!(register_value(100, false), None);
+        assert_eq!(register_value(180_000, false), None);
+        assert_eq!(register_value(512_000, false), None);
+    }
+
+    #[test]
+    fn test_watchdog_timeout() {
+        for millis in [250, 7_750, 10_000, 60_000, 496_000] {
+            let wd = WatchdogConfig {
+                timeout: Duration::from_millis(millis),
+                repeat: false,
+            }
+            .register()
+            .unwrap();
+
+            assert_eq!(wd.timeout(), Duration::from_millis(millis));
+        }
+    }
+
+    #[test]
+    fn test_watchdog() {
+        let expectations = [
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::WATCHDOG, 0xA9]),
+            // kicking writes the current value back unchanged
+            I2cTransaction::write_read(DEFAULT_ADDRESS, vec![Register::WATCHDOG], vec![0xA9]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::WATCHDOG, 0xA9]),
+            I2cTransaction::write(DEFAULT_ADDRESS, vec![Register::WATCHDOG, 0x00]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let mut rtc = Pcf85263a::new(I2cInterface::new(i2c, DEFAULT_ADDRESS));
+
+        rtc.configure_watchdog(&WatchdogConfig {
+            timeout: Duration::from_secs(10),
+            repeat: true,
+        })
+        .unwrap();
+        rtc.kick_watchdog().unwrap();
+        rtc.disable_watchdog().unwrap();
+
+        assert!(matches!(
+            rtc.configure_watchdog(&WatchdogConfig {
+                timeout: Duration::from_millis(100),
+                repeat: false,
+            }),
+            Err(Error::InvalidWatchdogTimeout)
+        ));
+
+        rtc.release().release().done();
+    }
+}